@@ -9,11 +9,15 @@ set -x
 cargo clippy -- -Dwarnings && \
 cargo clippy --no-default-features -- -Dwarnings && \
 cargo clippy --no-default-features --features serde -- -Dwarnings && \
+cargo clippy --features rayon -- -Dwarnings && \
+cargo clippy --features std -- -Dwarnings && \
 cargo +nightly clippy --all-features && \
 
 cargo clippy --tests -- -Dwarnings && \
 cargo clippy --tests --no-default-features -- -Dwarnings && \
 cargo clippy --tests --no-default-features --features serde -- -Dwarnings && \
+cargo clippy --tests --features rayon -- -Dwarnings && \
+cargo clippy --tests --features std -- -Dwarnings && \
 cargo +nightly clippy --tests --all-features && \
 
 # Check against a target that does _not_ support `std` to ensure it doesn't 
@@ -27,6 +31,8 @@ cargo +nightly clippy --target aarch64-unknown-none --features serde && \
 cargo test && \
 cargo test --no-default-features && \
 cargo test --no-default-features --features serde && \
+cargo test --features rayon && \
+cargo test --features std && \
 cargo +nightly test --all-features && \
 
 # Documentation
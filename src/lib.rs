@@ -1,6 +1,9 @@
 #![doc = include_str!("../README.md")]
-#![cfg_attr(not(any(test, doc)), no_std)]
-#![cfg_attr(feature = "nightly", feature(trusted_len, try_reserve_kind))]
+#![cfg_attr(not(any(test, doc, feature = "std")), no_std)]
+#![cfg_attr(
+    feature = "nightly",
+    feature(extend_one, trusted_len, try_reserve_kind)
+)]
 #![cfg_attr(any(docsrs, feature = "nightly"), feature(doc_cfg))]
 
 extern crate alloc;
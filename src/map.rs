@@ -1,8 +1,13 @@
-use alloc::{collections::TryReserveError, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, TryReserveError},
+    vec::Vec,
+};
 use core::{
     borrow::Borrow,
+    cmp::Ordering,
     fmt::{self, Debug},
     iter::FusedIterator,
+    ops::AddAssign,
 };
 
 /// `Map` is a data structure with a [`HashMap`]-like API but based on a `Vec`.
@@ -34,7 +39,7 @@ impl<K, V> Default for Map<K, V> {
 }
 
 impl<K: Eq, V> Map<K, V> {
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
             backing: Vec::new(),
         }
@@ -46,14 +51,177 @@ impl<K: Eq, V> Map<K, V> {
         }
     }
 
+    /// Creates an empty map, ignoring `hasher`.
+    ///
+    /// This exists so that `Map` can stand in for a [`HashMap`](std::collections::HashMap) at call
+    /// sites that pass a [`BuildHasher`](core::hash::BuildHasher). No hashing is ever done.
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    pub fn with_hasher<S: core::hash::BuildHasher>(hasher: S) -> Self {
+        let _ = hasher;
+        Self::new()
+    }
+
+    /// Creates an empty map with at least the specified capacity, ignoring `hasher`.
+    ///
+    /// See [`with_hasher`](Self::with_hasher).
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    pub fn with_capacity_and_hasher<S: core::hash::BuildHasher>(
+        capacity: usize,
+        hasher: S,
+    ) -> Self {
+        let _ = hasher;
+        Self::with_capacity(capacity)
+    }
+
+    /// Creates a map by copying borrowed entries, keeping the last value of any repeated key.
+    ///
+    /// This is `collect` for an iterator of `(&K, &V)`, such as [`iter`](Self::iter). It is not
+    /// a `FromIterator` impl because that would stop `map.iter().collect::<Map<_, _>>()` from
+    /// inferring a `Map<&K, &V>`.
+    pub fn from_copied<'a, I: IntoIterator<Item = (&'a K, &'a V)>>(iter: I) -> Self
+    where
+        K: 'a + Copy,
+        V: 'a + Copy,
+    {
+        iter.into_iter().map(|(k, v)| (*k, *v)).collect()
+    }
+
+    /// Creates a map from a `Vec` of entries without checking it for duplicate keys.
+    ///
+    /// # Safety
+    ///
+    /// `entries` must not contain any two keys that compare equal. Lookups and removals on a map
+    /// that violates this will silently ignore all but one of the duplicates. In debug builds,
+    /// this is checked and violations panic.
+    pub unsafe fn from_vec_unchecked(entries: Vec<(K, V)>) -> Self {
+        let this = Self { backing: entries };
+        #[cfg(debug_assertions)]
+        this.debug_assert_unique();
+        this
+    }
+
+    /// Creates a map from a `Vec` of entries, failing if any key appears more than once.
+    ///
+    /// Unlike `From<Vec<(K, V)>>`, which silently keeps the last of any duplicates, this is
+    /// useful when a repeated key indicates a bug in the input.
+    pub fn try_from_vec(entries: Vec<(K, V)>) -> Result<Self, DuplicateKey> {
+        let this = Self { backing: entries };
+        match this.first_duplicate() {
+            Some(err) => Err(err),
+            None => Ok(this),
+        }
+    }
+
+    fn first_duplicate(&self) -> Option<DuplicateKey> {
+        self.backing.iter().enumerate().find_map(|(i, (k, _))| {
+            self.backing[i + 1..]
+                .iter()
+                .position(|(l, _)| l == k)
+                .map(|j| DuplicateKey {
+                    first: i,
+                    index: i + 1 + j,
+                })
+        })
+    }
+
+    /// Panics if the map contains two equal keys, which can only happen if it was built through
+    /// one of the unchecked constructors.
+    #[cfg(debug_assertions)]
+    fn debug_assert_unique(&self) {
+        if let Some(DuplicateKey { first, index }) = self.first_duplicate() {
+            panic!(
+                "Map contains duplicate keys at indices {} and {}",
+                first, index
+            );
+        }
+    }
+
+    /// Returns whether `f` returns `true` for every entry, stopping at the first that fails.
+    ///
+    /// This is `true` for an empty map.
+    pub fn all_values<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.backing.iter().all(|(k, v)| f(k, v))
+    }
+
+    /// Returns whether `f` returns `true` for any entry, stopping at the first that does.
+    ///
+    /// This is `false` for an empty map.
+    pub fn any_value<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.backing.iter().any(|(k, v)| f(k, v))
+    }
+
+    /// Returns a raw pointer to the map's entries, which are stored contiguously as `len()`
+    /// `(K, V)` tuples.
+    ///
+    /// The pointer is valid until the map is next modified. Note that Rust does not guarantee the
+    /// field layout of tuples, so code on the other side of an FFI boundary should not assume
+    /// one unless it is otherwise known.
+    pub fn as_ptr(&self) -> *const (K, V) {
+        self.backing.as_ptr()
+    }
+
+    /// Returns the map's entries as a contiguous slice, in iteration order.
+    pub fn as_slice(&self) -> &[(K, V)] {
+        &self.backing
+    }
+
+    /// Binary searches the map's entries with a comparator function.
+    ///
+    /// `f` should return whether an entry is `Less`, `Equal` or `Greater` than the target. The map
+    /// must be sorted consistently with it (for example, by value if `f` compares values),
+    /// otherwise the result is unspecified. Returns `Ok` with the index of a matching entry, or
+    /// `Err` with the index where a matching entry could be inserted to keep the map sorted.
+    pub fn binary_search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&K, &V) -> Ordering,
+    {
+        self.backing.binary_search_by(|(k, v)| f(k, v))
+    }
+
+    /// Binary searches the map's keys for `key`.
+    ///
+    /// The map must be sorted by key (for example, by having only ever been inserted into in
+    /// ascending key order), otherwise the result is unspecified. Returns `Ok` with the index of
+    /// the matching entry, or `Err` with the index where the key could be inserted to keep the
+    /// map sorted.
+    pub fn binary_search_keys<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.backing.binary_search_by(|(k, _)| k.borrow().cmp(key))
+    }
+
     pub fn capacity(&self) -> usize {
         self.backing.capacity()
     }
 
+    /// Returns an iterator over the entries in slices of `size` entries, in iteration order. The
+    /// last slice is shorter if `size` does not divide the length of the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks(&self, size: usize) -> core::slice::Chunks<'_, (K, V)> {
+        self.backing.chunks(size)
+    }
+
     pub fn clear(&mut self) {
         self.backing.clear()
     }
 
+    pub fn clear_and_shrink(&mut self) {
+        self.backing = Vec::new();
+    }
+
     pub fn contains_key<Q>(&self, key: &Q) -> bool
     where
         K: Borrow<Q>,
@@ -62,10 +230,96 @@ impl<K: Eq, V> Map<K, V> {
         self.keys().any(|k| key.eq(k.borrow()))
     }
 
-    pub fn drain(&mut self) -> alloc::vec::Drain<(K, V)> {
+    pub fn contains_keys<'a, Q, I>(&self, keys: I) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized + 'a,
+        I: IntoIterator<Item = &'a Q>,
+    {
+        keys.into_iter().all(|key| self.contains_key(key))
+    }
+
+    /// Returns a cursor pointing at the first entry of the map, or at the ghost position if the
+    /// map is empty.
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, K, V> {
+        CursorMut {
+            index: 0,
+            backing: &mut self.backing,
+        }
+    }
+
+    /// Returns the entries of this map whose keys are not present in `other`.
+    pub fn difference_keys<'a>(
+        &'a self,
+        other: &'a Map<K, V>,
+    ) -> impl Iterator<Item = (&'a K, &'a V)> + 'a {
+        self.iter().filter(move |(k, _)| !other.contains_key(*k))
+    }
+
+    pub fn drain(&mut self) -> alloc::vec::Drain<'_, (K, V)> {
         self.backing.drain(..)
     }
 
+    /// Removes the entries for which `f` returns `true` and returns an iterator over them.
+    ///
+    /// Like [`drain`](Self::drain), the matching entries are removed when this is called, even if
+    /// the returned iterator is not consumed. Both the drained and the remaining entries keep their
+    /// original relative order.
+    pub fn drain_if<F>(&mut self, f: F) -> alloc::vec::IntoIter<(K, V)>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.drain_matching(f).backing.into_iter()
+    }
+
+    /// Removes the entries for which `f` returns `true` and returns them as a new map.
+    ///
+    /// Both maps keep their entries in their original relative order.
+    pub fn drain_matching<F>(&mut self, mut f: F) -> Map<K, V>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let capacity = self.backing.capacity();
+        let entries = core::mem::replace(&mut self.backing, Vec::with_capacity(capacity));
+        let mut matching = Vec::new();
+        for (k, v) in entries {
+            if f(&k, &v) {
+                matching.push((k, v));
+            } else {
+                self.backing.push((k, v));
+            }
+        }
+        Self { backing: matching }
+    }
+
+    /// Removes all but the first of consecutive entries for which `f` returns equal values.
+    ///
+    /// Like [`Vec::dedup_by_key`], this only removes adjacent duplicates, so the entries should
+    /// first be sorted by the same projection (e.g. through [`entries_mut`](Self::entries_mut))
+    /// for all duplicates to be removed.
+    pub fn dedup_by_key<T, F>(&mut self, mut f: F)
+    where
+        T: PartialEq,
+        F: FnMut(&K, &V) -> T,
+    {
+        self.backing.dedup_by_key(|(k, v)| f(k, v));
+    }
+
+    /// Returns the map's entries as a contiguous slice, in iteration order.
+    ///
+    /// This is the same as [`as_slice`](Self::as_slice).
+    pub fn entries(&self) -> &[(K, V)] {
+        &self.backing
+    }
+
+    /// Returns the map's entries as a contiguous mutable slice, in iteration order.
+    ///
+    /// This allows reordering entries, e.g. by sorting them. Modifying keys through it must not
+    /// make two keys compare equal, otherwise lookups will only find one of them.
+    pub fn entries_mut(&mut self) -> &mut [(K, V)] {
+        &mut self.backing
+    }
+
     pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
         match self.backing.iter_mut().position(|(k, _)| *k == key) {
             Some(pos) => Entry::Occupied(OccupiedEntry {
@@ -82,6 +336,42 @@ impl<K: Eq, V> Map<K, V> {
         }
     }
 
+    /// Returns `true` if the map holds exactly the entries of `other`, in any order.
+    ///
+    /// This is not a `PartialEq` impl because `==` between two maps compares their order.
+    pub fn eq_btree_map(&self, other: &BTreeMap<K, V>) -> bool
+    where
+        K: Ord,
+        V: PartialEq,
+    {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+
+    /// Returns `true` if the map holds exactly the entries of `other`, in any order.
+    ///
+    /// Like [`eq_btree_map`](Self::eq_btree_map), this is not a `PartialEq` impl because `==`
+    /// between two maps compares their order.
+    pub fn eq_unordered(&self, other: &[(K, V)]) -> bool
+    where
+        V: PartialEq,
+    {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(k, v)| other.iter().any(|(ok, ov)| ok == k && ov == v))
+    }
+
+    /// Returns the first entry in iteration order, which is the oldest one if no entries have
+    /// been removed or reordered.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.backing.first().map(|(k, v)| (k, v))
+    }
+
+    /// Returns the first entry in iteration order, with a mutable reference to its value.
+    pub fn first_mut(&mut self) -> Option<(&K, &mut V)> {
+        self.backing.first_mut().map(|(k, v)| (&*k, v))
+    }
+
     pub fn get<Q>(&self, key: &Q) -> Option<&V>
     where
         K: Borrow<Q>,
@@ -93,6 +383,28 @@ impl<K: Eq, V> Map<K, V> {
             .map(|(_, v)| v)
     }
 
+    /// Returns mutable references to the values corresponding to two distinct keys.
+    ///
+    /// Returns `None` if either key is missing or if both keys refer to the same entry.
+    pub fn get2_mut<Q>(&mut self, a: &Q, b: &Q) -> Option<(&mut V, &mut V)>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let a = self.position_of(a)?;
+        let b = self.position_of(b)?;
+
+        if a < b {
+            let (front, back) = self.backing.split_at_mut(b);
+            Some((&mut front[a].1, &mut back[0].1))
+        } else if b < a {
+            let (front, back) = self.backing.split_at_mut(a);
+            Some((&mut back[0].1, &mut front[b].1))
+        } else {
+            None
+        }
+    }
+
     pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
     where
         K: Borrow<Q>,
@@ -115,6 +427,98 @@ impl<K: Eq, V> Map<K, V> {
             .map(|(_, v)| v)
     }
 
+    /// Returns a reference to the value corresponding to the key, and swaps the entry with the
+    /// first entry of the map so that later lookups of the same key find it immediately.
+    ///
+    /// This is useful when the map is used as a small cache with a few hot keys. Note that this
+    /// changes the map's iteration order.
+    pub fn get_mru<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let pos = self.position_of(key)?;
+        self.backing.swap(0, pos);
+        Some(&self.backing[0].1)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key, inserting the default
+    /// value if it is absent, along with whether it was newly inserted.
+    pub fn get_or_insert_default(&mut self, key: K) -> (&mut V, bool)
+    where
+        V: Default,
+    {
+        self.get_or_insert_with_flag(key, V::default)
+    }
+
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &mut V {
+        self.get_or_insert_with_flag(key, f).0
+    }
+
+    /// Like [`get_or_insert_with`](Self::get_or_insert_with), but also returns whether the value
+    /// was newly inserted.
+    pub fn get_or_insert_with_flag<F: FnOnce() -> V>(&mut self, key: K, f: F) -> (&mut V, bool) {
+        let (pos, inserted) = match self.position_of(&key) {
+            Some(pos) => (pos, false),
+            None => {
+                self.backing.push((key, f()));
+                (self.backing.len() - 1, true)
+            }
+        };
+        (&mut self.backing[pos].1, inserted)
+    }
+
+    pub fn get_or_insert_with_key<F: FnOnce(&K) -> V>(&mut self, key: K, f: F) -> &mut V {
+        let pos = match self.position_of(&key) {
+            Some(pos) => pos,
+            None => {
+                let value = f(&key);
+                self.backing.push((key, value));
+                self.backing.len() - 1
+            }
+        };
+        &mut self.backing[pos].1
+    }
+
+    /// Inserts a key-value pair into the map if the key is absent, returning a mutable reference
+    /// to the inserted value.
+    ///
+    /// If the key is already present, nothing is updated and an error containing the occupied
+    /// entry and the rejected value is returned.
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<&mut V, OccupiedError<'_, K, V>> {
+        match self.entry(key) {
+            Entry::Occupied(entry) => Err(OccupiedError { entry, value }),
+            Entry::Vacant(entry) => Ok(entry.insert(value)),
+        }
+    }
+
+    pub fn try_get_or_insert_with<E, F>(&mut self, key: K, f: F) -> Result<&mut V, E>
+    where
+        F: FnOnce() -> Result<V, E>,
+    {
+        let pos = match self.position_of(&key) {
+            Some(pos) => pos,
+            None => {
+                self.backing.push((key, f()?));
+                self.backing.len() - 1
+            }
+        };
+        Ok(&mut self.backing[pos].1)
+    }
+
+    /// Adds one to the value corresponding to the key, inserting `1` if it is absent.
+    ///
+    /// This is shorthand for the common tallying pattern `*map.entry(key).or_insert(0) += 1`.
+    pub fn increment(&mut self, key: K)
+    where
+        V: AddAssign + From<u8>,
+    {
+        match self.position_of(&key) {
+            Some(pos) => self.backing[pos].1 += V::from(1),
+            None => self.backing.push((key, V::from(1))),
+        }
+    }
+
     pub fn insert(&mut self, key: K, value: V) -> Option<V> {
         match self.get_mut(&key) {
             Some(v) => Some(core::mem::replace(v, value)),
@@ -125,6 +529,36 @@ impl<K: Eq, V> Map<K, V> {
         }
     }
 
+    /// Inserts a key-value pair into a map whose entries are sorted by key, keeping them sorted.
+    ///
+    /// Returns the index of the entry and, if the key was already present, its previous value.
+    /// The map must already be sorted by key (e.g. by [`sort_and_dedup`](Self::sort_and_dedup)
+    /// or by only ever inserting through this method). Mixing this with plain
+    /// [`insert`](Self::insert), which appends, breaks that invariant.
+    pub fn insert_sorted(&mut self, key: K, value: V) -> (usize, Option<V>)
+    where
+        K: Ord,
+    {
+        match self.backing.binary_search_by(|(k, _)| k.cmp(&key)) {
+            Ok(pos) => (
+                pos,
+                Some(core::mem::replace(&mut self.backing[pos].1, value)),
+            ),
+            Err(pos) => {
+                self.backing.insert(pos, (key, value));
+                (pos, None)
+            }
+        }
+    }
+
+    /// Returns the entries of this map whose keys are also present in `other`.
+    pub fn intersection_keys<'a>(
+        &'a self,
+        other: &'a Map<K, V>,
+    ) -> impl Iterator<Item = (&'a K, &'a V)> + 'a {
+        self.iter().filter(move |(k, _)| other.contains_key(*k))
+    }
+
     pub fn is_empty(&self) -> bool {
         self.backing.is_empty()
     }
@@ -141,33 +575,275 @@ impl<K: Eq, V> Map<K, V> {
         }
     }
 
+    /// Returns an iterator over the entries in ascending key order, with mutable references to
+    /// the values.
+    ///
+    /// Unlike [`sort_and_dedup`](Self::sort_and_dedup), this does not reorder the map itself. It
+    /// allocates a `Vec` of references and sorts that instead.
+    pub fn iter_mut_sorted(&mut self) -> impl Iterator<Item = (&K, &mut V)>
+    where
+        K: Ord,
+    {
+        let mut entries: Vec<_> = self.backing.iter_mut().map(|(k, v)| (&*k, v)).collect();
+        entries.sort_by_key(|(k, _)| *k);
+        entries.into_iter()
+    }
+
     pub fn keys(&self) -> Keys<'_, K, V> {
         Keys { iter: self.iter() }
     }
 
+    /// Returns a `Vec` of clones of the map's keys, in iteration order.
+    pub fn keys_vec(&self) -> Vec<K>
+    where
+        K: Clone,
+    {
+        let mut keys = Vec::with_capacity(self.len());
+        keys.extend(self.keys().cloned());
+        keys
+    }
+
+    /// Returns the last entry in iteration order, which is the most recently inserted one if no
+    /// entries have been removed or reordered.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.backing.last().map(|(k, v)| (k, v))
+    }
+
+    /// Returns the last entry in iteration order, with a mutable reference to its value.
+    pub fn last_mut(&mut self) -> Option<(&K, &mut V)> {
+        self.backing.last_mut().map(|(k, v)| (&*k, v))
+    }
+
     pub fn len(&self) -> usize {
         self.backing.len()
     }
 
+    /// Moves the entry at index `from` to index `to`, shifting the entries in between.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` or `to` is out of bounds.
+    pub fn move_index(&mut self, from: usize, to: usize) {
+        if from < to {
+            self.backing[from..=to].rotate_left(1);
+        } else {
+            self.backing[to..=from].rotate_right(1);
+        }
+    }
+
+    /// Moves the entry with the given key to the back of the map, shifting the entries after it
+    /// forward. Returns whether the key was present.
+    pub fn move_to_back<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        match self.position_of(key) {
+            Some(pos) => {
+                self.move_index(pos, self.len() - 1);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the entry with the given key to the front of the map, shifting the entries before it
+    /// back. Returns whether the key was present.
+    pub fn move_to_front<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        match self.position_of(key) {
+            Some(pos) => {
+                self.move_index(pos, 0);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn partition<F>(self, mut f: F) -> (Self, Self)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        // Keys are already unique, so neither half needs to be de-duplicated.
+        let (matching, rest) = self.backing.into_iter().partition(|(k, v)| f(k, v));
+        (Self { backing: matching }, Self { backing: rest })
+    }
+
+    /// Removes a key from the map, returning its value if it was present.
+    ///
+    /// This is equivalent to [`swap_remove`](Self::swap_remove) and does not preserve the order
+    /// of the remaining entries. Use [`shift_remove`](Self::shift_remove) if order matters.
     pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
     where
         K: Borrow<Q>,
         Q: Eq + ?Sized,
     {
-        self.remove_entry(key).map(|(_, v)| v)
+        self.swap_remove(key)
     }
 
+    /// Removes a key from the map, returning the stored key and value if it was present.
+    ///
+    /// This is equivalent to [`swap_remove_entry`](Self::swap_remove_entry) and does not preserve
+    /// the order of the remaining entries.
     pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
     where
         K: Borrow<Q>,
         Q: Eq + ?Sized,
     {
-        self.backing
-            .iter()
-            .position(|(k, _)| key.eq(k.borrow()))
+        self.swap_remove_entry(key)
+    }
+
+    /// Returns the index of the first entry for which `pred` returns `false`.
+    ///
+    /// The map must be partitioned by `pred`, meaning that it returns `true` for every entry up
+    /// to some index and `false` for every entry after it (as is the case for a monotonic
+    /// predicate over a sorted map), otherwise the result is unspecified.
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&K, &V) -> bool,
+    {
+        self.backing.partition_point(|(k, v)| pred(k, v))
+    }
+
+    /// Returns the index of the entry with the given key, if it is present.
+    pub fn position_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.backing.iter().position(|(k, _)| key.eq(k.borrow()))
+    }
+
+    /// Removes every key in `keys` from the map, returning how many of them were present.
+    ///
+    /// This makes a single pass over the map, preserving the order of the remaining entries.
+    pub fn remove_all<'a, Q, I>(&mut self, keys: I) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized + 'a,
+        I: IntoIterator<Item = &'a Q>,
+    {
+        let keys: Vec<&Q> = keys.into_iter().collect();
+        if keys.is_empty() {
+            return 0;
+        }
+
+        let len = self.len();
+        self.backing.retain(|(k, _)| !keys.contains(&k.borrow()));
+        len - self.len()
+    }
+
+    /// Removes the key and returns its value if it is present, or otherwise inserts the value
+    /// returned by `f` and returns `None`.
+    ///
+    /// Like [`remove`](Self::remove), this does not preserve the order of the remaining entries.
+    pub fn remove_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> Option<V> {
+        match self.position_of(&key) {
+            Some(pos) => Some(self.backing.swap_remove(pos).1),
+            None => {
+                self.backing.push((key, f()));
+                None
+            }
+        }
+    }
+
+    /// Removes the entry at `index`, returning it if the index was in bounds.
+    ///
+    /// This is equivalent to [`swap_remove_index`](Self::swap_remove_index) and does not preserve
+    /// the order of the remaining entries.
+    pub fn remove_index(&mut self, index: usize) -> Option<(K, V)> {
+        self.swap_remove_index(index)
+    }
+
+    /// Sorts the entries by key and removes any entries with duplicate keys.
+    ///
+    /// The sort is stable, so of several entries with equal keys, the one that came first in the
+    /// map's previous order is kept.
+    pub fn sort_and_dedup(&mut self)
+    where
+        K: Ord,
+    {
+        self.backing.sort_by(|(a, _), (b, _)| a.cmp(b));
+        self.backing.dedup_by(|(a, _), (b, _)| a == b);
+    }
+
+    /// Removes a key from the map by swapping it with the last entry, returning its value if it
+    /// was present.
+    ///
+    /// This is O(1) once the key is found, but does not preserve the order of the remaining
+    /// entries.
+    pub fn swap_remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.swap_remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map by swapping it with the last entry, returning the stored key
+    /// and value if it was present.
+    ///
+    /// This is O(1) once the key is found, but does not preserve the order of the remaining
+    /// entries.
+    pub fn swap_remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position_of(key)
             .map(|pos| self.backing.swap_remove(pos))
     }
 
+    /// Removes the entry at `index` by swapping it with the last entry.
+    ///
+    /// This does not preserve the order of the remaining entries, but is O(1).
+    pub fn swap_remove_index(&mut self, index: usize) -> Option<(K, V)> {
+        (index < self.backing.len()).then(|| self.backing.swap_remove(index))
+    }
+
+    /// Removes a key from the map by shifting all following entries down by one, returning its
+    /// value if it was present.
+    ///
+    /// This preserves the order of the remaining entries, but is O(n).
+    pub fn shift_remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.shift_remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map by shifting all following entries down by one, returning the
+    /// stored key and value if it was present.
+    ///
+    /// This preserves the order of the remaining entries, but is O(n).
+    pub fn shift_remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position_of(key).map(|pos| self.backing.remove(pos))
+    }
+
+    /// Removes the entry at `index` by shifting all following entries down by one.
+    ///
+    /// This preserves the order of the remaining entries, but is O(n).
+    pub fn shift_remove_index(&mut self, index: usize) -> Option<(K, V)> {
+        (index < self.backing.len()).then(|| self.backing.remove(index))
+    }
+
+    /// Replaces the stored key that is equal to `key` with `key`, returning the old key.
+    ///
+    /// The value is left untouched. If no equal key is present, the map is unchanged and `None`
+    /// is returned. This is useful for keys that carry data which doesn't take part in `Eq`.
+    pub fn replace_key(&mut self, key: K) -> Option<K> {
+        let pos = self.position_of(&key)?;
+        Some(core::mem::replace(&mut self.backing[pos].0, key))
+    }
+
     pub fn reserve(&mut self, additional: usize) {
         self.backing.reserve(additional);
     }
@@ -179,20 +855,95 @@ impl<K: Eq, V> Map<K, V> {
         self.backing.retain_mut(|(k, v)| f(k, v));
     }
 
+    /// Retains only the entries whose keys `f` returns `true` for.
+    pub fn retain_keys<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K) -> bool,
+    {
+        self.backing.retain(|(k, _)| f(k));
+    }
+
+    /// Retains only the entries for which `f` returns `true`, then shrinks the capacity if less
+    /// than half of it is in use.
+    ///
+    /// Plain [`retain`](Self::retain) never releases memory, which can waste a lot of it in a
+    /// long-lived map after most of its entries have been removed.
+    pub fn retain_and_shrink<F>(&mut self, f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.retain(f);
+        if self.len() < self.capacity() / 2 {
+            self.shrink_to_fit();
+        }
+    }
+
+    /// Retains only the entries for which `f` returns `true`, returning the removed entries as a
+    /// new map.
+    ///
+    /// This is [`drain_matching`](Self::drain_matching) with the predicate inverted.
+    pub fn retain_into<F>(&mut self, mut f: F) -> Map<K, V>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.drain_matching(|k, v| !f(k, v))
+    }
+
     pub fn shrink_to_fit(&mut self) {
         self.backing.shrink_to_fit();
     }
 
+    pub fn update<Q, F>(&mut self, key: &Q, f: F) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+        F: FnOnce(&mut V),
+    {
+        match self.get_mut(key) {
+            Some(v) => {
+                f(v);
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn values(&self) -> Values<'_, K, V> {
         Values { iter: self.iter() }
     }
 
+    /// Returns a `Vec` of clones of the map's values, in iteration order.
+    pub fn values_vec(&self) -> Vec<V>
+    where
+        V: Clone,
+    {
+        let mut values = Vec::with_capacity(self.len());
+        values.extend(self.values().cloned());
+        values
+    }
+
     pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
         ValuesMut {
             iter: self.iter_mut(),
         }
     }
 
+    /// Returns an iterator over all overlapping windows of `size` consecutive entries.
+    ///
+    /// The windows reflect the current storage order, so comparing neighboring keys is mostly
+    /// useful after sorting, e.g. with [`sort_and_dedup`](Self::sort_and_dedup).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn windows(&self, size: usize) -> core::slice::Windows<'_, (K, V)> {
+        self.backing.windows(size)
+    }
+
+    /// Shrinks the capacity of the map with a lower bound.
+    ///
+    /// The capacity will remain at least as large as both the length and `min_capacity`, so
+    /// passing a `min_capacity` below the length is fine.
     pub fn shrink_to(&mut self, min_capacity: usize) {
         self.backing.shrink_to(min_capacity)
     }
@@ -200,6 +951,10 @@ impl<K: Eq, V> Map<K, V> {
     pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
         self.backing.try_reserve(additional)
     }
+
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.backing.try_reserve_exact(additional)
+    }
 }
 
 impl<K: Debug, V: Debug> fmt::Debug for Map<K, V> {
@@ -269,18 +1024,42 @@ impl<K: Eq, V> FromIterator<(K, V)> for Map<K, V> {
 
 impl<K: Eq, V> Extend<(K, V)> for Map<K, V> {
     fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
         for (k, v) in iter {
             self.insert(k, v);
         }
     }
+
+    #[cfg(feature = "nightly")]
+    fn extend_one(&mut self, (k, v): (K, V)) {
+        self.insert(k, v);
+    }
+
+    #[cfg(feature = "nightly")]
+    fn extend_reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
 }
 
 impl<'a, K: 'a + Copy + Eq, V: 'a + Copy> Extend<(&'a K, &'a V)> for Map<K, V> {
     fn extend<T: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
         for (k, v) in iter {
             self.insert(*k, *v);
         }
     }
+
+    #[cfg(feature = "nightly")]
+    fn extend_one(&mut self, (k, v): (&'a K, &'a V)) {
+        self.insert(*k, *v);
+    }
+
+    #[cfg(feature = "nightly")]
+    fn extend_reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
 }
 
 impl<K: Eq, V, T: Into<Vec<(K, V)>>> From<T> for Map<K, V> {
@@ -306,10 +1085,15 @@ pub struct Keys<'a, K, V> {
     iter: Iter<'a, K, V>,
 }
 
-impl<K, V> Keys<'_, K, V> {
-    fn map_item<'a>(item: (&'a K, &'a V)) -> &'a K {
+impl<'a, K, V> Keys<'a, K, V> {
+    fn map_item(item: (&'a K, &'a V)) -> &'a K {
         item.0
     }
+
+    /// Returns the remaining entries, both keys and values, as a slice.
+    pub fn as_slice(&self) -> &'a [(K, V)] {
+        self.iter.as_slice()
+    }
 }
 
 impl<'a, K, V> Iterator for Keys<'a, K, V> {
@@ -342,10 +1126,15 @@ pub struct Values<'a, K, V> {
     iter: Iter<'a, K, V>,
 }
 
-impl<K, V> Values<'_, K, V> {
-    fn map_item<'a>(item: (&'a K, &'a V)) -> &'a V {
+impl<'a, K, V> Values<'a, K, V> {
+    fn map_item(item: (&'a K, &'a V)) -> &'a V {
         item.1
     }
+
+    /// Returns the remaining entries, both keys and values, as a slice.
+    pub fn as_slice(&self) -> &'a [(K, V)] {
+        self.iter.as_slice()
+    }
 }
 
 impl<'a, K, V> Iterator for Values<'a, K, V> {
@@ -418,6 +1207,10 @@ impl<'a, K, V> Iter<'a, K, V> {
     fn map_item(item: &'a (K, V)) -> (&'a K, &'a V) {
         (&item.0, &item.1)
     }
+
+    pub fn as_slice(&self) -> &'a [(K, V)] {
+        self.iter.as_slice()
+    }
 }
 
 impl<'a, K, V> Iterator for Iter<'a, K, V> {
@@ -454,6 +1247,14 @@ impl<'a, K, V> IterMut<'a, K, V> {
     fn map_item(item: &'a mut (K, V)) -> (&'a mut K, &'a mut V) {
         (&mut item.0, &mut item.1)
     }
+
+    pub fn as_slice(&self) -> &[(K, V)] {
+        self.iter.as_slice()
+    }
+
+    pub fn into_slice(self) -> &'a mut [(K, V)] {
+        self.iter.into_slice()
+    }
 }
 
 impl<'a, K, V> Iterator for IterMut<'a, K, V> {
@@ -514,6 +1315,13 @@ impl<'a, K, V> Entry<'a, K, V> {
             Entry::Vacant(ve) => ve.insert(f()),
         }
     }
+
+    pub fn or_try_insert_with<E>(self, f: impl FnOnce() -> Result<V, E>) -> Result<&'a mut V, E> {
+        match self {
+            Entry::Occupied(oe) => Ok(oe.into_mut()),
+            Entry::Vacant(ve) => Ok(ve.insert(f()?)),
+        }
+    }
 }
 
 impl<'a, K: 'a, V: Default> Entry<'a, K, V> {
@@ -525,59 +1333,251 @@ impl<'a, K: 'a, V: Default> Entry<'a, K, V> {
         )]
         self.or_insert(V::default())
     }
-}
+}
+
+pub struct OccupiedEntry<'a, K: 'a, V: 'a> {
+    entry_pos: usize,
+    backing: &'a mut Vec<(K, V)>,
+}
+
+impl<'a, K: 'a, V: 'a> OccupiedEntry<'a, K, V> {
+    pub fn get(&self) -> &V {
+        &self.backing[self.entry_pos].1
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.backing[self.entry_pos].1
+    }
+
+    pub fn insert(&mut self, value: V) -> V {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    pub fn into_key_value(self) -> (&'a K, &'a V) {
+        let (k, v) = &self.backing[self.entry_pos];
+        (k, v)
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.backing[self.entry_pos].1
+    }
+
+    pub fn key(&self) -> &K {
+        &self.backing[self.entry_pos].0
+    }
+
+    pub fn key_value(&self) -> (&K, &V) {
+        let (k, v) = &self.backing[self.entry_pos];
+        (k, v)
+    }
+
+    /// Removes the entry from the map, returning its value.
+    ///
+    /// This is equivalent to [`shift_remove`](Self::shift_remove) and preserves the order of the
+    /// remaining entries.
+    pub fn remove(self) -> V {
+        self.shift_remove()
+    }
+
+    /// Removes the entry by swapping it with the last entry, returning its value.
+    ///
+    /// This is O(1), but does not preserve the order of the remaining entries.
+    pub fn swap_remove(self) -> V {
+        self.backing.swap_remove(self.entry_pos).1
+    }
+
+    /// Removes the entry by shifting all following entries down by one, returning its value.
+    ///
+    /// This preserves the order of the remaining entries, but is O(n).
+    pub fn shift_remove(self) -> V {
+        self.backing.remove(self.entry_pos).1
+    }
+}
+
+pub struct VacantEntry<'a, K: 'a, V: 'a> {
+    key: K,
+    backing: &'a mut Vec<(K, V)>,
+}
+
+impl<'a, K: 'a, V: 'a> VacantEntry<'a, K, V> {
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.backing.push((self.key, value));
+        &mut self.backing.last_mut().unwrap().1
+    }
+
+    pub fn insert_entry(self, value: V) -> OccupiedEntry<'a, K, V> {
+        self.backing.push((self.key, value));
+        OccupiedEntry {
+            entry_pos: self.backing.len() - 1,
+            backing: self.backing,
+        }
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+/// A cursor over a [`Map`] that can move back and forth and edit the map in place.
+///
+/// The cursor points either at an entry or at a "ghost" position between the last and the first
+/// entry. Moving past either end of the map lands on the ghost position, and moving again wraps
+/// around to the other end. It is created with [`Map::cursor_mut`].
+pub struct CursorMut<'a, K: 'a, V: 'a> {
+    index: usize,
+    backing: &'a mut Vec<(K, V)>,
+}
+
+impl<'a, K: 'a, V: 'a> CursorMut<'a, K, V> {
+    fn next_index(&self) -> usize {
+        if self.index < self.backing.len() {
+            self.index + 1
+        } else {
+            0
+        }
+    }
+
+    fn prev_index(&self) -> usize {
+        if self.index == 0 {
+            self.backing.len()
+        } else {
+            self.index - 1
+        }
+    }
+
+    /// Returns the index of the current entry, or `None` at the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        (self.index < self.backing.len()).then(|| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        self.index = self.next_index();
+    }
+
+    pub fn move_prev(&mut self) {
+        self.index = self.prev_index();
+    }
 
-pub struct OccupiedEntry<'a, K: 'a, V: 'a> {
-    entry_pos: usize,
-    backing: &'a mut Vec<(K, V)>,
-}
+    pub fn current(&mut self) -> Option<(&K, &mut V)> {
+        self.backing.get_mut(self.index).map(|(k, v)| (&*k, v))
+    }
 
-impl<'a, K: 'a, V: 'a> OccupiedEntry<'a, K, V> {
-    pub fn get(&self) -> &V {
-        &self.backing[self.entry_pos].1
+    pub fn peek_next(&mut self) -> Option<(&K, &mut V)> {
+        let index = self.next_index();
+        self.backing.get_mut(index).map(|(k, v)| (&*k, v))
     }
 
-    pub fn get_mut(&mut self) -> &mut V {
-        &mut self.backing[self.entry_pos].1
+    pub fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
+        let index = self.prev_index();
+        self.backing.get_mut(index).map(|(k, v)| (&*k, v))
     }
 
-    pub fn insert(&mut self, value: V) -> V {
-        core::mem::replace(self.get_mut(), value)
+    /// Removes the current entry, returning it.
+    ///
+    /// This is equivalent to [`swap_remove_current`](Self::swap_remove_current) and does not
+    /// preserve the order of the remaining entries.
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        self.swap_remove_current()
     }
 
-    pub fn into_mut(self) -> &'a mut V {
-        &mut self.backing[self.entry_pos].1
+    /// Removes the current entry by swapping it with the last entry, returning it.
+    ///
+    /// The cursor is left pointing at the entry that was swapped into place, or at the ghost
+    /// position if the current entry was the last one.
+    pub fn swap_remove_current(&mut self) -> Option<(K, V)> {
+        (self.index < self.backing.len()).then(|| self.backing.swap_remove(self.index))
     }
 
-    pub fn key(&self) -> &K {
-        &self.backing[self.entry_pos].0
+    /// Removes the current entry by shifting all following entries down by one, returning it.
+    ///
+    /// The cursor is left pointing at the entry that followed the removed one.
+    pub fn shift_remove_current(&mut self) -> Option<(K, V)> {
+        (self.index < self.backing.len()).then(|| self.backing.remove(self.index))
     }
+}
 
-    pub fn remove(self) -> V {
-        self.backing.remove(self.entry_pos).1
+impl<'a, K: 'a + Eq, V: 'a> CursorMut<'a, K, V> {
+    /// Inserts an entry after the current one, or at the front of the map if the cursor is at the
+    /// ghost position. The cursor does not move.
+    ///
+    /// If the key is already present, its value is updated in place instead and the old value is
+    /// returned, as with [`Map::insert`].
+    pub fn insert_after(&mut self, key: K, value: V) -> Option<V> {
+        if let Some((_, v)) = self.backing.iter_mut().find(|(k, _)| *k == key) {
+            return Some(core::mem::replace(v, value));
+        }
+        let index = self.next_index();
+        self.backing.insert(index, (key, value));
+        if index <= self.index {
+            self.index += 1;
+        }
+        None
     }
 }
 
-pub struct VacantEntry<'a, K: 'a, V: 'a> {
-    key: K,
-    backing: &'a mut Vec<(K, V)>,
+/// The error returned by [`Map::try_from_vec`] when a key appears more than once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DuplicateKey {
+    /// The index of the first entry with the repeated key.
+    pub first: usize,
+    /// The index of the entry that repeats it.
+    pub index: usize,
 }
 
-impl<'a, K: 'a, V: 'a> VacantEntry<'a, K, V> {
-    pub fn insert(self, value: V) -> &'a mut V {
-        self.backing.push((self.key, value));
-        &mut self.backing.last_mut().unwrap().1
+impl fmt::Display for DuplicateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "duplicate key at index {} (first seen at index {})",
+            self.index, self.first
+        )
     }
+}
 
-    pub fn into_key(self) -> K {
-        self.key
+#[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+impl std::error::Error for DuplicateKey {}
+
+/// The error returned by [`Map::try_insert`] when the key is already present.
+///
+/// It contains the occupied entry and the value that was not inserted.
+pub struct OccupiedError<'a, K: 'a, V: 'a> {
+    /// The entry in the map that was already occupied.
+    pub entry: OccupiedEntry<'a, K, V>,
+    /// The value which was not inserted, because the entry was already occupied.
+    pub value: V,
+}
+
+impl<K: Debug, V: Debug> fmt::Debug for OccupiedError<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OccupiedError")
+            .field("key", self.entry.key())
+            .field("old_value", self.entry.get())
+            .field("new_value", &self.value)
+            .finish()
     }
+}
 
-    pub fn key(&self) -> &K {
-        &self.key
+impl<K: Debug, V: Debug> fmt::Display for OccupiedError<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to insert {:?}, key {:?} already exists with value {:?}",
+            self.value,
+            self.entry.key(),
+            self.entry.get(),
+        )
     }
 }
 
+#[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+impl<K: Debug, V: Debug> std::error::Error for OccupiedError<'_, K, V> {}
+
 #[cfg(feature = "serde")]
 mod map_serde {
     use core::{fmt, marker::PhantomData};
@@ -688,19 +1688,112 @@ mod map_serde {
     }
 }
 
+#[cfg(feature = "rayon")]
+mod map_rayon {
+    use alloc::vec::Vec;
+
+    use rayon::prelude::*;
+
+    use super::Map;
+
+    /// Collects the items of the parallel iterator into a `Vec` and looks each key up in the map,
+    /// both in parallel. The values of existing keys are then replaced and new keys appended in
+    /// one sequential pass, so as with [`Extend`], later values overwrite earlier ones with the
+    /// same key.
+    ///
+    /// The sequential pass only compares new keys with each other; the scan of the existing
+    /// entries is what runs in parallel.
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "rayon")))]
+    impl<K: Eq + Send + Sync, V: Send + Sync> ParallelExtend<(K, V)> for Map<K, V> {
+        fn par_extend<I>(&mut self, par_iter: I)
+        where
+            I: IntoParallelIterator<Item = (K, V)>,
+        {
+            let entries: Vec<(K, V)> = par_iter.into_par_iter().collect();
+            let positions: Vec<Option<usize>> = entries
+                .par_iter()
+                .map(|(k, _)| self.position_of(k))
+                .collect();
+
+            let len = self.len();
+            for ((k, v), pos) in entries.into_iter().zip(positions) {
+                let pos = pos.or_else(|| {
+                    let new = &self.backing[len..];
+                    new.iter().position(|(n, _)| *n == k).map(|i| len + i)
+                });
+                match pos {
+                    Some(pos) => self.backing[pos].1 = v,
+                    None => self.backing.push((k, v)),
+                }
+            }
+        }
+    }
+
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "rayon")))]
+    impl<K: Eq + Send, V: Send> Map<K, V> {
+        /// Returns a parallel iterator over mutable references to the map's values.
+        pub fn par_values_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut V> {
+            self.backing.par_iter_mut().map(|(_, v)| v)
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use pretty_assertions::assert_eq;
+        use rayon::prelude::*;
+
+        use super::Map;
+
+        #[test]
+        fn test_par_values_mut() {
+            let mut m: Map<u64, u64> = (0..1000).map(|i| (i, i)).collect();
+
+            m.par_values_mut().for_each(|v| *v *= *v);
+
+            assert_eq!(m.len(), 1000);
+            assert!(m.iter().all(|(k, v)| *v == k * k));
+        }
+
+        #[test]
+        fn test_par_extend() {
+            let mut m: Map<u64, u64> = (0..100).map(|i| (i, 0)).collect();
+
+            m.par_extend((0..2000).into_par_iter().map(|i| (i % 1000, i)));
+
+            assert_eq!(m.len(), 1000);
+            assert!(m.iter().all(|(k, v)| *v == k + 1000));
+            assert_eq!(m.keys().take(3).copied().collect::<Vec<_>>(), [0, 1, 2]);
+        }
+    }
+}
+
 // taken from libstd/collections/hash/map.rs @ 7454b2
 #[cfg(test)]
 mod test {
+    use alloc::collections::BTreeMap;
     use core::cell::RefCell;
 
     use pretty_assertions::assert_eq;
     use rand::{thread_rng, Rng};
 
     use super::{
+        DuplicateKey,
         Entry::{Occupied, Vacant},
         Map,
     };
 
+    /// Compares equal on its first field only, so tests can tell which of two equal values is kept.
+    #[derive(Debug)]
+    struct Foo(&'static str, i32);
+
+    impl PartialEq for Foo {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Foo {}
+
     #[test]
     fn test_zero_capacities() {
         type M = Map<i32, i32>;
@@ -727,6 +1820,24 @@ mod test {
         assert_eq!(m.capacity(), 0);
     }
 
+    #[test]
+    fn test_const_new() {
+        static EMPTY: Map<&str, i32> = Map::new();
+
+        assert_eq!(EMPTY.len(), 0);
+        assert!(EMPTY.is_empty());
+    }
+
+    #[test]
+    fn test_clear_and_shrink() {
+        let mut m: Map<_, _> = (0..100).map(|i| (i, i)).collect();
+        assert!(m.capacity() >= 100);
+
+        m.clear_and_shrink();
+        assert_eq!(m.len(), 0);
+        assert_eq!(m.capacity(), 0);
+    }
+
     #[test]
     fn test_create_capacity_zero() {
         let mut m = Map::with_capacity(0);
@@ -737,6 +1848,19 @@ mod test {
         assert!(!m.contains_key(&0));
     }
 
+    #[test]
+    fn test_contains_keys() {
+        let m: Map<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+
+        assert!(m.contains_keys(&[0, 2, 4]));
+        assert!(m.contains_keys(&[]));
+        assert!(!m.contains_keys(&[0, 5, 4]));
+
+        let m: Map<String, u32> = [("a".to_string(), 1), ("b".to_string(), 2)].into();
+        assert!(m.contains_keys(["a", "b"]));
+        assert!(!m.contains_keys(["a", "c"]));
+    }
+
     #[test]
     fn test_insert() {
         let mut m = Map::new();
@@ -794,6 +1918,162 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_intersection_keys() {
+        let defaults = Map::from([("color", "auto"), ("pager", "less"), ("editor", "vi")]);
+        let user = Map::from([("editor", "emacs"), ("theme", "dark"), ("color", "never")]);
+
+        let overridden: Vec<_> = user.intersection_keys(&defaults).collect();
+        assert_eq!(overridden, [(&"editor", &"emacs"), (&"color", &"never")]);
+
+        let overridden: Vec<_> = defaults.intersection_keys(&user).collect();
+        assert_eq!(overridden, [(&"color", &"auto"), (&"editor", &"vi")]);
+
+        assert_eq!(user.intersection_keys(&Map::new()).next(), None);
+    }
+
+    #[test]
+    fn test_difference_keys() {
+        let defaults = Map::from([("color", "auto"), ("pager", "less"), ("editor", "vi")]);
+        let user = Map::from([("editor", "emacs"), ("theme", "dark"), ("color", "never")]);
+
+        let added: Vec<_> = user.difference_keys(&defaults).collect();
+        assert_eq!(added, [(&"theme", &"dark")]);
+
+        let untouched: Vec<_> = defaults.difference_keys(&user).collect();
+        assert_eq!(untouched, [(&"pager", &"less")]);
+
+        assert_eq!(user.difference_keys(&Map::new()).count(), user.len());
+    }
+
+    #[test]
+    fn test_cursor_mut_walk() {
+        let mut m = Map::from([(1, 'a'), (2, 'b'), (3, 'c')]);
+        let mut cursor = m.cursor_mut();
+
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some((&1, &mut 'a')));
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(cursor.peek_next(), Some((&2, &mut 'b')));
+
+        cursor.move_next();
+        *cursor.current().unwrap().1 = 'B';
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some((&3, &mut 'c')));
+
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some((&1, &mut 'a')));
+
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+
+        assert_eq!(m.as_slice(), [(1, 'a'), (2, 'B'), (3, 'c')]);
+    }
+
+    #[test]
+    fn test_cursor_mut_remove() {
+        let mut m: Map<_, _> = (0..6).map(|i| (i, i * 10)).collect();
+        let mut cursor = m.cursor_mut();
+
+        while let Some((k, _)) = cursor.current() {
+            if k % 2 == 0 {
+                cursor.shift_remove_current();
+            } else {
+                cursor.move_next();
+            }
+        }
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [1, 3, 5]);
+
+        let mut cursor = m.cursor_mut();
+        assert_eq!(cursor.remove_current(), Some((1, 10)));
+        assert_eq!(cursor.current(), Some((&5, &mut 50)));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some((&3, &mut 30)));
+        assert_eq!(cursor.swap_remove_current(), Some((3, 30)));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+
+        assert_eq!(m.as_slice(), [(5, 50)]);
+    }
+
+    #[test]
+    fn test_cursor_mut_insert_after() {
+        let mut m = Map::from([(1, 'a'), (3, 'c')]);
+        let mut cursor = m.cursor_mut();
+
+        assert_eq!(cursor.insert_after(2, 'b'), None);
+        assert_eq!(cursor.current(), Some((&1, &mut 'a')));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some((&2, &mut 'b')));
+
+        assert_eq!(cursor.insert_after(3, 'C'), Some('c'));
+
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.insert_after(0, 'z'), None);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_next(), Some((&0, &mut 'z')));
+
+        assert_eq!(m.as_slice(), [(0, 'z'), (1, 'a'), (2, 'b'), (3, 'C')]);
+    }
+
+    #[test]
+    fn test_drain_if() {
+        let mut m: Map<_, _> = (0..10).map(|i| (i, i * 10)).collect();
+
+        let mut drained = m.drain_if(|k, _| k % 2 == 0);
+        assert_eq!(drained.next(), Some((0, 0)));
+        assert_eq!(drained.len(), 4);
+        drop(drained);
+
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [1, 3, 5, 7, 9]);
+        assert_eq!(m.drain_if(|_, _| false).next(), None);
+        assert_eq!(m.len(), 5);
+    }
+
+    #[test]
+    fn test_drain_matching() {
+        let mut odd: Map<_, _> = (0..10).map(|i| (i, i * 10)).collect();
+        let capacity = odd.capacity();
+
+        let even = odd.drain_matching(|k, _| k % 2 == 0);
+        assert_eq!(odd.capacity(), capacity);
+        assert_eq!(even.keys().copied().collect::<Vec<_>>(), [0, 2, 4, 6, 8]);
+        assert_eq!(odd.keys().copied().collect::<Vec<_>>(), [1, 3, 5, 7, 9]);
+        assert_eq!(even[&4], 40);
+        assert_eq!(odd[&5], 50);
+
+        assert!(odd.drain_matching(|_, &v| v > 100).is_empty());
+        assert_eq!(odd.len(), 5);
+    }
+
+    #[test]
+    fn test_entries() {
+        let mut m = Map::from([(3, 'c'), (1, 'a'), (2, 'b')]);
+
+        for ((k, v), (ek, ev)) in m.iter().zip(m.entries()) {
+            assert_eq!((k, v), (ek, ev));
+        }
+
+        m.entries_mut().sort_by_key(|&(k, _)| k);
+        assert_eq!(m.entries(), [(1, 'a'), (2, 'b'), (3, 'c')]);
+        assert_eq!(m.entries().binary_search_by(|(k, _)| k.cmp(&2)), Ok(1));
+        assert_eq!(m.entries().binary_search_by(|(k, _)| k.cmp(&4)), Err(3));
+
+        m.entries_mut()[0].1 = 'z';
+        assert_eq!(m[&1], 'z');
+    }
+
     #[test]
     fn test_drops() {
         DROP_VECTOR.with(|slot| {
@@ -1010,17 +2290,290 @@ mod test {
     // }
 
     #[test]
-    fn test_find_mut() {
-        let mut m = Map::new();
-        assert!(m.insert(1, 12).is_none());
-        assert!(m.insert(2, 8).is_none());
-        assert!(m.insert(5, 14).is_none());
-        let new = 100;
-        match m.get_mut(&5) {
-            None => panic!(),
-            Some(x) => *x = new,
+    fn test_from_vec_unchecked() {
+        // SAFETY: The keys are unique.
+        let m = unsafe { Map::from_vec_unchecked(vec![(3, 'c'), (1, 'a')]) };
+        assert_eq!(m.len(), 2);
+        assert_eq!(m[&1], 'a');
+        assert_eq!(m, Map::from([(3, 'c'), (1, 'a')]));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Map contains duplicate keys at indices 1 and 2")]
+    fn test_from_vec_unchecked_duplicates() {
+        // SAFETY: Not safe! Debug builds must catch this.
+        let _ = unsafe { Map::from_vec_unchecked(vec![(3, 'c'), (1, 'a'), (1, 'b')]) };
+    }
+
+    #[test]
+    fn test_any_all_values() {
+        let m = Map::from([("a", 1), ("b", 2), ("c", 3)]);
+
+        assert!(m.any_value(|_, &v| v == 2));
+        assert!(!m.any_value(|&k, _| k == "d"));
+        assert!(m.all_values(|_, &v| v > 0));
+        assert!(!m.all_values(|_, &v| v < 3));
+
+        let mut calls = 0;
+        assert!(m.any_value(|_, _| {
+            calls += 1;
+            true
+        }));
+        assert_eq!(calls, 1);
+
+        let empty: Map<&str, i32> = Map::new();
+        assert!(!empty.any_value(|_, _| true));
+        assert!(empty.all_values(|_, _| false));
+    }
+
+    #[test]
+    fn test_as_slice_and_ptr() {
+        let m = Map::from([(1, 'a'), (2, 'b'), (3, 'c')]);
+
+        assert_eq!(m.as_slice(), [(1, 'a'), (2, 'b'), (3, 'c')]);
+
+        let (ptr, len) = (m.as_ptr(), m.len());
+        // SAFETY: `ptr` points to `len` initialized entries, and `m` is not modified while the
+        // slice is alive.
+        let entries = unsafe { core::slice::from_raw_parts(ptr, len) };
+        assert_eq!(entries, m.as_slice());
+        assert_eq!(entries[1], (2, 'b'));
+    }
+
+    #[test]
+    fn test_binary_search_by() {
+        let m = Map::from([("d", 1), ("a", 3), ("c", 4), ("b", 8)]);
+
+        assert_eq!(m.binary_search_by(|_, v| v.cmp(&4)), Ok(2));
+        assert_eq!(m.binary_search_by(|_, v| v.cmp(&1)), Ok(0));
+        assert_eq!(m.binary_search_by(|_, v| v.cmp(&5)), Err(3));
+        assert_eq!(m.binary_search_by(|_, v| v.cmp(&9)), Err(4));
+    }
+
+    #[test]
+    fn test_binary_search_keys() {
+        let m: Map<_, _> = [(1, 'a'), (3, 'b'), (5, 'c'), (7, 'd')].into();
+
+        assert_eq!(m.binary_search_keys(&1), Ok(0));
+        assert_eq!(m.binary_search_keys(&5), Ok(2));
+        assert_eq!(m.binary_search_keys(&0), Err(0));
+        assert_eq!(m.binary_search_keys(&4), Err(2));
+        assert_eq!(m.binary_search_keys(&8), Err(4));
+
+        let m: Map<String, u32> = [("a".to_string(), 1), ("c".to_string(), 2)].into();
+        assert_eq!(m.binary_search_keys("c"), Ok(1));
+        assert_eq!(m.binary_search_keys("b"), Err(1));
+    }
+
+    #[test]
+    fn test_get2_mut() {
+        let mut m = Map::from([("a", 1), ("b", 2), ("c", 3)]);
+
+        let (a, c) = m.get2_mut("a", "c").unwrap();
+        core::mem::swap(a, c);
+        assert_eq!(m["a"], 3);
+        assert_eq!(m["c"], 1);
+
+        let (c, b) = m.get2_mut("c", "b").unwrap();
+        *c += 10;
+        *b += 20;
+        assert_eq!(m["b"], 22);
+        assert_eq!(m["c"], 11);
+
+        assert_eq!(m.get2_mut("a", "d"), None);
+        assert_eq!(m.get2_mut("d", "a"), None);
+    }
+
+    #[test]
+    fn test_get2_mut_same_key() {
+        let mut m = Map::from([("a", 1), ("b", 2)]);
+        assert_eq!(m.get2_mut("a", "a"), None);
+    }
+
+    #[test]
+    fn test_get_mru() {
+        let mut m: Map<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+
+        assert_eq!(m.get_mru(&3), Some(&30));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [3, 1, 2, 0, 4]);
+
+        assert_eq!(m.get_mru(&3), Some(&30));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [3, 1, 2, 0, 4]);
+
+        assert_eq!(m.get_mru(&4), Some(&40));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [4, 1, 2, 0, 3]);
+
+        assert_eq!(m.get_mru(&5), None);
+        assert_eq!(m.len(), 5);
+    }
+
+    #[test]
+    fn test_get_or_insert_default() {
+        let mut m: Map<&str, u32> = Map::new();
+
+        let (v, inserted) = m.get_or_insert_default("a");
+        assert!(inserted);
+        assert_eq!(*v, 0);
+        *v += 1;
+
+        let (v, inserted) = m.get_or_insert_default("a");
+        assert!(!inserted);
+        assert_eq!(*v, 1);
+
+        assert_eq!(m.len(), 1);
+    }
+
+    #[test]
+    fn test_get_or_insert_with() {
+        let mut m = Map::new();
+        m.insert(1, 10);
+
+        *m.get_or_insert_with(1, || panic!("called for an existing key")) += 1;
+        assert_eq!(m[&1], 11);
+
+        assert_eq!(*m.get_or_insert_with(2, || 20), 20);
+        assert_eq!(m.len(), 2);
+        assert_eq!(m[&2], 20);
+    }
+
+    #[test]
+    fn test_find_mut() {
+        let mut m = Map::new();
+        assert!(m.insert(1, 12).is_none());
+        assert!(m.insert(2, 8).is_none());
+        assert!(m.insert(5, 14).is_none());
+        let new = 100;
+        match m.get_mut(&5) {
+            None => panic!(),
+            Some(x) => *x = new,
+        }
+        assert_eq!(m.get(&5), Some(&new));
+    }
+
+    #[test]
+    fn test_get_or_insert_with_key() {
+        let mut m = Map::new();
+
+        assert_eq!(m.get_or_insert_with_key("ab", |k| k.repeat(2)), "abab");
+        assert_eq!(m["ab"], "abab");
+
+        m.get_or_insert_with_key("ab", |_| panic!("called for an existing key"))
+            .push('!');
+        assert_eq!(m["ab"], "abab!");
+        assert_eq!(m.len(), 1);
+    }
+
+    #[test]
+    fn test_get_or_insert_with_flag() {
+        let mut m = Map::new();
+
+        let (v, inserted) = m.get_or_insert_with_flag("a", || 1);
+        assert!(inserted);
+        *v += 1;
+
+        let (v, inserted) = m.get_or_insert_with_flag("a", || panic!("called for an existing key"));
+        assert!(!inserted);
+        assert_eq!(*v, 2);
+
+        assert_eq!(m.len(), 1);
+    }
+
+    #[test]
+    fn test_try_insert() {
+        let mut m = Map::new();
+
+        assert_eq!(m.try_insert(1, 'a').ok().copied(), Some('a'));
+
+        let mut err = m.try_insert(1, 'b').unwrap_err();
+        assert_eq!(err.entry.key(), &1);
+        assert_eq!(err.entry.get(), &'a');
+        assert_eq!(err.value, 'b');
+        assert_eq!(
+            format!("{}", err),
+            "failed to insert 'b', key 1 already exists with value 'a'"
+        );
+        assert_eq!(
+            format!("{:?}", err),
+            "OccupiedError { key: 1, old_value: 'a', new_value: 'b' }"
+        );
+
+        *err.entry.get_mut() = 'c';
+        assert_eq!(m[&1], 'c');
+    }
+
+    #[test]
+    fn test_try_get_or_insert_with() {
+        let mut m = Map::new();
+
+        assert_eq!(m.try_get_or_insert_with(1, || Err("nope")), Err("nope"));
+        assert!(m.is_empty());
+
+        assert_eq!(m.try_get_or_insert_with(1, || Ok::<_, ()>(10)), Ok(&mut 10));
+        assert_eq!(m[&1], 10);
+
+        let v = m.try_get_or_insert_with(1, || -> Result<_, ()> {
+            panic!("called for an existing key")
+        });
+        assert_eq!(v, Ok(&mut 10));
+        assert_eq!(m.len(), 1);
+    }
+
+    #[test]
+    fn test_update() {
+        let mut m = Map::new();
+        m.insert("a", 1);
+
+        assert!(m.update("a", |v| *v += 1));
+        assert_eq!(m["a"], 2);
+
+        assert!(!m.update("b", |_| panic!("called for a missing key")));
+        assert_eq!(m.len(), 1);
+    }
+
+    #[test]
+    fn test_increment() {
+        let mut counts: Map<char, u32> = Map::new();
+        for c in "abracadabra".chars() {
+            counts.increment(c);
         }
-        assert_eq!(m.get(&5), Some(&new));
+        assert_eq!(
+            counts.as_slice(),
+            [('a', 5), ('b', 2), ('r', 2), ('c', 1), ('d', 1)]
+        );
+
+        let mut counts: Map<&str, f64> = Map::new();
+        counts.increment("x");
+        counts.increment("x");
+        assert_eq!(counts["x"], 2.0);
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        let mut m = Map::new();
+
+        assert_eq!(m.insert_sorted(5, 'a'), (0, None));
+        assert_eq!(m.insert_sorted(1, 'b'), (0, None));
+        assert_eq!(m.insert_sorted(9, 'c'), (2, None));
+        assert_eq!(m.insert_sorted(3, 'd'), (1, None));
+        assert_eq!(m.insert_sorted(5, 'e'), (2, Some('a')));
+
+        assert_eq!(m.as_slice(), [(1, 'b'), (3, 'd'), (5, 'e'), (9, 'c')]);
+        assert_eq!(m.binary_search_keys(&9), Ok(3));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_with_hasher() {
+        use std::collections::hash_map::RandomState;
+
+        let mut m = Map::with_hasher(RandomState::new());
+        m.insert(1, 'a');
+        assert_eq!(m[&1], 'a');
+
+        let m: Map<i32, char> = Map::with_capacity_and_hasher(10, RandomState::new());
+        assert!(m.is_empty());
+        assert!(m.capacity() >= 10);
     }
 
     #[test]
@@ -1085,6 +2638,238 @@ mod test {
         assert_eq!(m.remove(&1), None);
     }
 
+    #[test]
+    fn test_move_index() {
+        let mut m: Map<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+
+        m.move_index(0, 2);
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [1, 2, 0, 3, 4]);
+        assert_eq!(m[&0], 0);
+
+        m.move_index(4, 1);
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [1, 4, 2, 0, 3]);
+
+        m.move_index(3, 3);
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [1, 4, 2, 0, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_move_index_out_of_bounds() {
+        let mut m: Map<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+        m.move_index(0, 5);
+    }
+
+    #[test]
+    fn test_move_to_front_back() {
+        let mut m: Map<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+
+        assert!(m.move_to_front(&2));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [2, 0, 1, 3, 4]);
+
+        assert!(m.move_to_back(&0));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [2, 1, 3, 4, 0]);
+
+        assert!(!m.move_to_front(&5));
+        assert!(!m.move_to_back(&5));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [2, 1, 3, 4, 0]);
+        assert_eq!(m[&2], 20);
+    }
+
+    #[test]
+    fn test_partition() {
+        let m: Map<_, _> = (0..10).map(|i| (i, i * 10)).collect();
+
+        let (even, odd) = m.partition(|k, _| k % 2 == 0);
+        assert_eq!(
+            even,
+            (0..10)
+                .step_by(2)
+                .map(|i| (i, i * 10))
+                .collect::<Map<_, _>>()
+        );
+        assert_eq!(
+            odd,
+            (1..10)
+                .step_by(2)
+                .map(|i| (i, i * 10))
+                .collect::<Map<_, _>>()
+        );
+    }
+
+    #[test]
+    fn test_partition_point() {
+        let m: Map<_, _> = (0..10).map(|i| (i * 2, i)).collect();
+
+        assert_eq!(m.partition_point(|&k, _| k < 7), 4);
+        assert_eq!(m.partition_point(|&k, _| k < 8), 4);
+        assert_eq!(m.partition_point(|_, &v| v < 0), 0);
+        assert_eq!(m.partition_point(|_, _| true), m.len());
+    }
+
+    #[test]
+    fn test_position_of() {
+        let m = Map::from([("a", 1), ("b", 2), ("c", 3)]);
+
+        assert_eq!(m.position_of("a"), Some(0));
+        assert_eq!(m.position_of("c"), Some(2));
+        assert_eq!(m.position_of("d"), None);
+    }
+
+    #[test]
+    fn test_remove_all() {
+        let mut m: Map<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+
+        assert_eq!(m.remove_all(&[1, 3, 7]), 2);
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [0, 2, 4]);
+
+        assert_eq!(m.remove_all(&[]), 0);
+        assert_eq!(m.len(), 3);
+
+        let mut m: Map<String, u32> = [("a".to_string(), 1), ("b".to_string(), 2)].into();
+        assert_eq!(m.remove_all(["a", "a", "c"]), 1);
+        assert_eq!(m.keys().collect::<Vec<_>>(), ["b"]);
+    }
+
+    #[test]
+    fn test_replace_key() {
+        let mut m = Map::new();
+        m.insert(Foo("a", 1), 'x');
+
+        let old = m.replace_key(Foo("a", 2)).unwrap();
+        assert_eq!(old.1, 1);
+
+        let (k, v) = m.get_key_value(&Foo("a", 0)).unwrap();
+        assert_eq!(k.1, 2);
+        assert_eq!(*v, 'x');
+
+        assert!(m.replace_key(Foo("b", 3)).is_none());
+        assert_eq!(m.len(), 1);
+    }
+
+    #[test]
+    fn test_remove_or_insert_with() {
+        let mut m = Map::from([("dark_mode", true)]);
+
+        assert_eq!(
+            m.remove_or_insert_with("dark_mode", || unreachable!()),
+            Some(true)
+        );
+        assert!(m.is_empty());
+
+        assert_eq!(m.remove_or_insert_with("dark_mode", || false), None);
+        assert_eq!(m["dark_mode"], false);
+    }
+
+    #[test]
+    fn test_remove_index() {
+        let mut m: Map<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+
+        let index = m.position_of(&1).unwrap();
+        assert_eq!(m.remove_index(index), Some((1, 10)));
+        assert_eq!(m.len(), 4);
+        assert!(!m.contains_key(&1));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [0, 4, 2, 3]);
+
+        let index = m.position_of(&4).unwrap();
+        assert_eq!(m.shift_remove_index(index), Some((4, 40)));
+        assert_eq!(m.len(), 3);
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [0, 2, 3]);
+
+        assert_eq!(m.remove_index(3), None);
+        assert_eq!(m.shift_remove_index(3), None);
+        assert_eq!(m.len(), 3);
+    }
+
+    #[test]
+    fn test_dedup_by_key() {
+        let mut m = Map::from([
+            (1, "banana"),
+            (2, "apple"),
+            (3, "cherry"),
+            (4, "avocado"),
+            (5, "blueberry"),
+        ]);
+
+        m.entries_mut().sort_by_key(|(_, v)| v.as_bytes()[0]);
+        m.dedup_by_key(|_, v| v.as_bytes()[0]);
+        assert_eq!(m.as_slice(), [(2, "apple"), (1, "banana"), (3, "cherry")]);
+
+        m.dedup_by_key(|_, _| ());
+        assert_eq!(m.as_slice(), [(2, "apple")]);
+    }
+
+    #[test]
+    fn test_sort_and_dedup() {
+        let mut m = Map {
+            backing: vec![(3, 'a'), (1, 'b'), (3, 'c'), (2, 'd'), (1, 'e')],
+        };
+
+        m.sort_and_dedup();
+        assert_eq!(m.backing, [(1, 'b'), (2, 'd'), (3, 'a')]);
+    }
+
+    #[test]
+    fn test_swap_remove_order() {
+        let mut m: Map<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+
+        assert_eq!(m.remove(&0), Some(0));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [4, 1, 2, 3]);
+
+        assert_eq!(m.swap_remove(&1), Some(10));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [4, 3, 2]);
+
+        assert_eq!(m.remove_entry(&4), Some((4, 40)));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [2, 3]);
+
+        assert_eq!(m.swap_remove_entry(&2), Some((2, 20)));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [3]);
+
+        assert_eq!(m.swap_remove(&2), None);
+        assert_eq!(m.swap_remove_entry(&2), None);
+    }
+
+    #[test]
+    fn test_shift_remove_order() {
+        let mut m: Map<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+
+        assert_eq!(m.shift_remove(&0), Some(0));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+
+        assert_eq!(m.shift_remove(&2), Some(20));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [1, 3, 4]);
+
+        assert_eq!(m.shift_remove(&2), None);
+
+        assert_eq!(m.shift_remove_entry(&1), Some((1, 10)));
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [3, 4]);
+
+        assert_eq!(m.shift_remove_entry(&1), None);
+    }
+
+    #[test]
+    fn test_occupied_entry_remove_order() {
+        let mut m: Map<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+
+        match m.entry(0) {
+            Vacant(_) => unreachable!(),
+            Occupied(view) => assert_eq!(view.remove(), 0),
+        }
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+
+        match m.entry(1) {
+            Vacant(_) => unreachable!(),
+            Occupied(view) => assert_eq!(view.swap_remove(), 10),
+        }
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [4, 2, 3]);
+
+        match m.entry(4) {
+            Vacant(_) => unreachable!(),
+            Occupied(view) => assert_eq!(view.shift_remove(), 40),
+        }
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [2, 3]);
+    }
+
     #[test]
     fn test_iterate() {
         let mut m = Map::with_capacity(4);
@@ -1164,6 +2949,21 @@ mod test {
         assert!(values.contains(&'c'));
     }
 
+    #[test]
+    fn test_keys_values_vec() {
+        let map = Map::from([(1, 'a'), (2, 'b'), (3, 'c')]);
+
+        let keys = map.keys_vec();
+        assert_eq!(keys, [1, 2, 3]);
+        assert_eq!(keys.capacity(), map.len());
+
+        let values = map.values_vec();
+        assert_eq!(values, ['a', 'b', 'c']);
+        assert_eq!(values.capacity(), map.len());
+
+        assert!(Map::<i32, char>::new().keys_vec().is_empty());
+    }
+
     #[test]
     fn test_values_mut() {
         let vec = vec![(1, 1), (2, 2), (3, 3)];
@@ -1178,6 +2978,28 @@ mod test {
         assert!(values.contains(&6));
     }
 
+    #[test]
+    fn test_first_last() {
+        let mut m = Map::new();
+        assert_eq!(m.first_key_value(), None);
+        assert_eq!(m.last_mut(), None);
+
+        m.insert("a", 1);
+        m.insert("b", 2);
+        m.insert("c", 3);
+
+        assert_eq!(m.first_key_value(), Some((&"a", &1)));
+        assert_eq!(m.last_key_value(), Some((&"c", &3)));
+
+        *m.last_mut().unwrap().1 += 10;
+        assert_eq!(m.last_key_value(), Some((&"c", &13)));
+
+        let (k, v) = m.first_mut().unwrap();
+        assert_eq!(*k, "a");
+        *v = 0;
+        assert_eq!(m["a"], 0);
+    }
+
     #[test]
     fn test_find() {
         let mut m = Map::new();
@@ -1283,6 +3105,46 @@ mod test {
         assert_eq!(map.capacity(), xs.len() - 1);
     }
 
+    #[test]
+    fn test_from_copied() {
+        let xs = [(1, 10), (2, 20), (1, 30)];
+
+        let map = Map::from_copied(xs.iter().map(|(k, v)| (k, v)));
+        assert_eq!(map, Map::from([(1, 30), (2, 20)]));
+
+        let copy = Map::from_copied(&map);
+        assert_eq!(copy, map);
+
+        let refs: Map<_, _> = map.iter().collect();
+        assert_eq!(refs.get(&1), Some(&&30));
+    }
+
+    #[test]
+    fn test_try_from_vec() {
+        let m = Map::try_from_vec(vec![(1, 'a'), (2, 'b'), (3, 'c')]).unwrap();
+        assert_eq!(m.as_slice(), [(1, 'a'), (2, 'b'), (3, 'c')]);
+
+        let err = Map::try_from_vec(vec![(1, 'a'), (2, 'b'), (3, 'c'), (2, 'd')]).unwrap_err();
+        assert_eq!(err, DuplicateKey { first: 1, index: 3 });
+        assert_eq!(
+            err.to_string(),
+            "duplicate key at index 3 (first seen at index 1)"
+        );
+    }
+
+    #[test]
+    fn test_from_iter_result() {
+        let xs: [Result<(i32, i32), &str>; 3] = [Ok((1, 10)), Ok((2, 20)), Ok((1, 30))];
+        let map: Result<Map<_, _>, _> = xs.iter().cloned().collect();
+        assert_eq!(map, Ok(Map::from([(1, 30), (2, 20)])));
+
+        let mut seen = 0;
+        let xs = [Ok((1, 10)), Err("bad"), Ok((2, 20))];
+        let map: Result<Map<_, _>, _> = xs.iter().inspect(|_| seen += 1).cloned().collect();
+        assert_eq!(map, Err("bad"));
+        assert_eq!(seen, 2);
+    }
+
     #[test]
     fn test_size_hint() {
         let xs = [(1, 1), (2, 2), (3, 3), (4, 4), (5, 5), (6, 6)];
@@ -1309,6 +3171,51 @@ mod test {
         assert_eq!(iter.len(), 3);
     }
 
+    #[test]
+    fn test_iter_as_slice() {
+        let xs = [(1, 1), (2, 2), (3, 3), (4, 4)];
+
+        let mut map: Map<_, _> = xs.iter().cloned().collect();
+
+        let mut iter = map.iter();
+        iter.next();
+        iter.next();
+        assert_eq!(iter.as_slice(), &xs[2..]);
+
+        let mut iter = map.iter_mut();
+        iter.next();
+        iter.next();
+        assert_eq!(iter.as_slice(), &xs[2..]);
+
+        let rest = iter.into_slice();
+        rest[0].1 = 30;
+        assert_eq!(map[&3], 30);
+
+        let mut iter = map.into_iter();
+        assert_eq!(iter.next(), Some((1, 1)));
+        assert_eq!(iter.as_slice(), [(2, 2), (3, 30), (4, 4)]);
+        iter.next_back();
+        assert_eq!(iter.as_slice(), [(2, 2), (3, 30)]);
+    }
+
+    #[test]
+    fn test_keys_values_as_slice() {
+        let xs = [(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')];
+
+        let map: Map<_, _> = xs.iter().cloned().collect();
+
+        let mut keys = map.keys();
+        keys.next();
+        assert_eq!(keys.as_slice().len(), keys.len());
+        assert_eq!(keys.as_slice(), &xs[1..]);
+
+        let mut values = map.values();
+        values.next();
+        values.next_back();
+        assert_eq!(values.as_slice().len(), values.len());
+        assert_eq!(values.as_slice(), &xs[1..3]);
+    }
+
     #[test]
     fn test_double_ended() {
         let xs = [(1, 1), (2, 2), (3, 3), (4, 4), (5, 5), (6, 6)];
@@ -1333,6 +3240,20 @@ mod test {
         assert_eq!(iter.size_hint(), (3, Some(3)));
     }
 
+    #[test]
+    fn test_iter_mut_sorted() {
+        let mut m = Map::from([(3, 0), (1, 0), (4, 0), (2, 0)]);
+
+        let mut visited = Vec::new();
+        for (i, (k, v)) in m.iter_mut_sorted().enumerate() {
+            visited.push(*k);
+            *v = i;
+        }
+
+        assert_eq!(visited, [1, 2, 3, 4]);
+        assert_eq!(m.as_slice(), [(3, 2), (1, 0), (4, 3), (2, 1)]);
+    }
+
     #[test]
     fn test_iter_mut_len() {
         let xs = [(1, 1), (2, 2), (3, 3), (4, 4), (5, 5), (6, 6)];
@@ -1430,6 +3351,27 @@ mod test {
         assert_eq!(map.len(), 6);
     }
 
+    #[test]
+    fn test_entry_or_try_insert_with() {
+        let mut map = Map::new();
+
+        assert_eq!(map.entry(1).or_try_insert_with(|| Err("nope")), Err("nope"));
+        assert!(map.is_empty());
+
+        assert_eq!(
+            map.entry(1).or_try_insert_with(|| Ok::<_, ()>(10)),
+            Ok(&mut 10)
+        );
+        assert_eq!(map[&1], 10);
+
+        assert_eq!(
+            map.entry(1)
+                .or_try_insert_with(|| -> Result<_, ()> { panic!("called for an existing key") }),
+            Ok(&mut 10)
+        );
+        assert_eq!(map.len(), 1);
+    }
+
     #[test]
     fn test_entry_take_doesnt_corrupt() {
         // Test for #19292
@@ -1477,6 +3419,68 @@ mod test {
         assert_eq!(a[&3], "three");
     }
 
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn test_extend_one() {
+        let mut a = Map::new();
+        Extend::<(i32, &str)>::extend_reserve(&mut a, 3);
+        assert!(a.capacity() >= 3);
+
+        a.extend_one((1, "one"));
+        a.extend_one((2, "two"));
+        a.extend_one((1, "uno"));
+        assert_eq!(a.as_slice(), [(1, "uno"), (2, "two")]);
+
+        a.extend_one((&3, &"three"));
+        assert_eq!(a[&3], "three");
+    }
+
+    #[test]
+    fn test_extend_reserves() {
+        let mut a = Map::new();
+        a.extend((0..1000).map(|i| (i, i)).collect::<Vec<_>>());
+        assert_eq!(a.len(), 1000);
+        assert_eq!(a.capacity(), 1000);
+
+        let mut b: Map<i32, i32> = Map::new();
+        b.extend(&a);
+        assert_eq!(b.len(), 1000);
+        assert_eq!(b.capacity(), 1000);
+    }
+
+    #[test]
+    fn test_chunks() {
+        let m: Map<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+
+        let chunks: Vec<_> = m.chunks(2).collect();
+        assert_eq!(
+            chunks.iter().map(|c| c.len()).collect::<Vec<_>>(),
+            [2, 2, 1]
+        );
+        assert_eq!(chunks[1], [(2, 20), (3, 30)]);
+        assert_eq!(chunks.concat(), m.as_slice());
+
+        assert_eq!(Map::<i32, i32>::new().chunks(2).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chunks_zero() {
+        let m = Map::from([(1, 'a')]);
+        let _ = m.chunks(0);
+    }
+
+    #[test]
+    fn test_windows() {
+        let mut m = Map::from([(7, 'd'), (1, 'a'), (2, 'b'), (5, 'c')]);
+        m.sort_and_dedup();
+
+        let gaps: Vec<_> = m.windows(2).map(|w| w[1].0 - w[0].0).collect();
+        assert_eq!(gaps, [1, 3, 2]);
+
+        assert_eq!(m.windows(5).count(), 0);
+    }
+
     #[test]
     fn test_capacity_not_less_than_len() {
         let mut a = Map::new();
@@ -1520,6 +3524,21 @@ mod test {
         assert_eq!(a[key], value);
     }
 
+    #[test]
+    fn test_occupied_entry_key_value() {
+        let mut a = Map::new();
+        a.insert("hello", 1);
+
+        let (k, v) = match a.entry("hello") {
+            Vacant(_) => panic!(),
+            Occupied(e) => {
+                assert_eq!(e.key_value(), (&"hello", &1));
+                e.into_key_value()
+            }
+        };
+        assert_eq!((k, v), (&"hello", &1));
+    }
+
     #[test]
     fn test_vacant_entry_key() {
         let mut a = Map::new();
@@ -1538,6 +3557,52 @@ mod test {
         assert_eq!(a[key], value);
     }
 
+    #[test]
+    fn test_vacant_entry_insert_entry() {
+        let mut a = Map::new();
+        a.insert("first", 1);
+
+        match a.entry("hello") {
+            Occupied(_) => panic!(),
+            Vacant(e) => {
+                let mut e = e.insert_entry(2);
+                assert_eq!(*e.key(), "hello");
+                assert_eq!(*e.get(), 2);
+                *e.get_mut() += 1;
+            }
+        }
+        assert_eq!(a.len(), 2);
+        assert_eq!(a["hello"], 3);
+    }
+
+    #[test]
+    fn test_shrink_to() {
+        let mut map: Map<i32, i32> = Map::with_capacity(100);
+        map.extend((0..10).map(|x| (x, x)));
+
+        map.shrink_to(20);
+        assert!(map.capacity() >= 20);
+
+        map.shrink_to(0);
+        assert!(map.capacity() >= map.len());
+        assert_eq!(map.len(), 10);
+        assert_eq!(map[&9], 9);
+    }
+
+    #[test]
+    fn test_retain_and_shrink() {
+        let mut map: Map<i32, i32> = (0..100).map(|x| (x, x)).collect();
+        assert_eq!(map.capacity(), 100);
+
+        map.retain_and_shrink(|&k, _| k >= 60);
+        assert_eq!(map.len(), 40);
+        assert_eq!(map.capacity(), 40);
+
+        map.retain_and_shrink(|&k, _| k >= 70);
+        assert_eq!(map.len(), 30);
+        assert_eq!(map.capacity(), 40);
+    }
+
     #[test]
     fn test_retain() {
         let mut map: Map<i32, i32> = (0..100).map(|x| (x, x * 10)).collect();
@@ -1549,6 +3614,27 @@ mod test {
         assert_eq!(map[&6], 60);
     }
 
+    #[test]
+    fn test_retain_keys() {
+        let mut map: Map<i32, char> = (0..6).zip('a'..).collect();
+
+        map.retain_keys(|&k| k % 2 == 0);
+        assert_eq!(map.as_slice(), [(0, 'a'), (2, 'c'), (4, 'e')]);
+    }
+
+    #[test]
+    fn test_retain_into() {
+        let mut map: Map<i32, i32> = (0..10).map(|x| (x, x * 10)).collect();
+
+        let removed = map.retain_into(|&k, _| k % 3 == 0);
+        assert_eq!(map.as_slice(), [(0, 0), (3, 30), (6, 60), (9, 90)]);
+        assert_eq!(
+            removed.keys().copied().collect::<Vec<_>>(),
+            [1, 2, 4, 5, 7, 8]
+        );
+        assert_eq!(removed[&5], 50);
+    }
+
     #[test]
     fn test_try_reserve() {
         let mut empty_bytes: Map<u8, u8> = Map::new();
@@ -1578,6 +3664,18 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_try_reserve_exact() {
+        let mut empty_bytes: Map<u8, u8> = Map::new();
+
+        empty_bytes
+            .try_reserve_exact(usize::MAX)
+            .expect_err("usize::MAX should trigger an overflow!");
+
+        empty_bytes.try_reserve_exact(10).unwrap();
+        assert!(empty_bytes.capacity() >= 10);
+    }
+
     #[test]
     fn test_debug_format() {
         let mut a = Map::<&str, usize>::default();
@@ -1590,6 +3688,29 @@ mod test {
         assert_eq!(r#"{"a": 1, "b": 2}"#, format!("{:?}", a));
     }
 
+    #[test]
+    fn test_eq_btree_map() {
+        let map = Map::from([(3, 'c'), (1, 'a'), (2, 'b')]);
+
+        assert!(map.eq_btree_map(&BTreeMap::from([(1, 'a'), (2, 'b'), (3, 'c')])));
+        assert!(!map.eq_btree_map(&BTreeMap::from([(1, 'a'), (2, 'b'), (3, 'x')])));
+        assert!(!map.eq_btree_map(&BTreeMap::from([(1, 'a'), (2, 'b')])));
+        assert!(!map.eq_btree_map(&BTreeMap::from([(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')])));
+    }
+
+    #[test]
+    fn test_eq_unordered() {
+        let map = Map::from([(3, 'c'), (1, 'a'), (2, 'b')]);
+
+        assert!(map.eq_unordered(&[(1, 'a'), (2, 'b'), (3, 'c')]));
+        assert!(map.eq_unordered(&[(2, 'b'), (3, 'c'), (1, 'a')]));
+
+        assert!(!map.eq_unordered(&[(1, 'a'), (2, 'b'), (3, 'x')]));
+        assert!(!map.eq_unordered(&[(1, 'a'), (2, 'b')]));
+        assert!(!map.eq_unordered(&[(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]));
+        assert!(!map.eq_unordered(&[(1, 'a'), (2, 'b'), (1, 'a')]));
+    }
+
     /// Ensures that, like `Vec`, `Default` works for `Map` even when its
     /// key/value types do not implement `Default`.
     #[test]
@@ -1,4 +1,4 @@
-use alloc::vec::Vec;
+use alloc::{borrow::ToOwned, collections::BTreeSet, vec::Vec};
 use core::{
     borrow::Borrow,
     fmt::{self, Debug},
@@ -40,7 +40,7 @@ impl<T> Default for Set<T> {
 }
 
 impl<T: Eq> Set<T> {
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
             backing: Vec::new(),
         }
@@ -52,10 +52,127 @@ impl<T: Eq> Set<T> {
         }
     }
 
+    /// Creates a set from an iterator, keeping the last of any equal values.
+    ///
+    /// `FromIterator` keeps the first of several equal values. This instead replaces it with each
+    /// later one, which matters when values carry data that doesn't take part in `Eq`. The
+    /// position of each value is that of its first occurrence.
+    pub fn from_iter_last<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut this = Self::with_capacity(iter.size_hint().0);
+        for value in iter {
+            this.replace(value);
+        }
+        this
+    }
+
+    /// Creates a set by copying borrowed values, keeping the first of any equal values.
+    ///
+    /// This is `collect` for an iterator of `&T`, such as [`iter`](Self::iter). It is not a
+    /// `FromIterator` impl because that would stop `set.iter().collect::<Set<_>>()` from
+    /// inferring a `Set<&T>`.
+    pub fn from_copied<'a, I: IntoIterator<Item = &'a T>>(iter: I) -> Self
+    where
+        T: 'a + Copy,
+    {
+        iter.into_iter().copied().collect()
+    }
+
+    /// Creates a set from a `Vec` without checking it for duplicates.
+    ///
+    /// # Safety
+    ///
+    /// `values` must not contain any two values that compare equal. Lookups and removals on a set
+    /// that violates this will silently ignore all but one of the duplicates. In debug builds,
+    /// this is checked and violations panic.
+    pub unsafe fn from_vec_unchecked(values: Vec<T>) -> Self {
+        let this = Self { backing: values };
+        #[cfg(debug_assertions)]
+        this.debug_assert_unique();
+        this
+    }
+
+    /// Creates a set from a `Vec` of values, failing if any value appears more than once.
+    ///
+    /// Unlike `From<Vec<T>>`, which silently drops duplicates, this is useful for checking that
+    /// external data really was unique.
+    pub fn try_from_vec(values: Vec<T>) -> Result<Self, DuplicateValue> {
+        let this = Self { backing: values };
+        match this.first_duplicate() {
+            Some(err) => Err(err),
+            None => Ok(this),
+        }
+    }
+
+    fn first_duplicate(&self) -> Option<DuplicateValue> {
+        self.backing.iter().enumerate().find_map(|(i, v)| {
+            self.backing[i + 1..]
+                .iter()
+                .position(|w| w == v)
+                .map(|j| DuplicateValue {
+                    first: i,
+                    index: i + 1 + j,
+                })
+        })
+    }
+
+    /// Panics if the set contains two equal values, which can only happen if it was built
+    /// through one of the unchecked constructors.
+    #[cfg(debug_assertions)]
+    fn debug_assert_unique(&self) {
+        if let Some(DuplicateValue { first, index }) = self.first_duplicate() {
+            panic!(
+                "Set contains duplicate values at indices {} and {}",
+                first, index
+            );
+        }
+    }
+
+    /// Returns whether `f` returns `true` for every value, stopping at the first that fails.
+    ///
+    /// This is `true` for an empty set.
+    pub fn all<F: FnMut(&T) -> bool>(&self, f: F) -> bool {
+        self.backing.iter().all(f)
+    }
+
+    /// Returns whether `f` returns `true` for any value, stopping at the first that does.
+    ///
+    /// This is `false` for an empty set.
+    pub fn any<F: FnMut(&T) -> bool>(&self, f: F) -> bool {
+        self.backing.iter().any(f)
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.backing
+    }
+
+    /// Binary searches the set for `value`.
+    ///
+    /// The set must be sorted (for example, by having only ever been inserted into in ascending
+    /// order), otherwise the result is unspecified. Returns `Ok` with the index of the matching
+    /// value, or `Err` with the index where the value could be inserted to keep the set sorted.
+    pub fn binary_search<Q>(&self, value: &Q) -> Result<usize, usize>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.backing.binary_search_by(|v| v.borrow().cmp(value))
+    }
+
     pub fn capacity(&self) -> usize {
         self.backing.capacity()
     }
 
+    /// Returns an iterator over the values in slices of `size` values, in iteration order. The
+    /// last slice is shorter if `size` does not divide the length of the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks(&self, size: usize) -> core::slice::Chunks<'_, T> {
+        self.backing.chunks(size)
+    }
+
     pub fn clear(&mut self) {
         self.backing.clear()
     }
@@ -68,6 +185,15 @@ impl<T: Eq> Set<T> {
         self.backing.iter().any(|v| value.eq(v.borrow()))
     }
 
+    /// Returns a cursor pointing at the first value of the set, or at the ghost position if the
+    /// set is empty.
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: 0,
+            backing: &mut self.backing,
+        }
+    }
+
     pub fn difference<'a>(&'a self, other: &'a Self) -> Difference<'a, T> {
         Difference {
             iter: self.iter(),
@@ -75,10 +201,43 @@ impl<T: Eq> Set<T> {
         }
     }
 
-    pub fn drain(&mut self) -> alloc::vec::Drain<T> {
+    /// Returns a new set with the values in `self` that are not in `other`.
+    ///
+    /// This is equivalent to `self - other`.
+    pub fn difference_cloned(&self, other: &Set<T>) -> Set<T>
+    where
+        T: Clone,
+    {
+        self.difference(other).cloned().collect()
+    }
+
+    /// Removes every value that is present in `other`, in place.
+    pub fn difference_with(&mut self, other: &Set<T>) {
+        self.backing.retain(|v| !other.contains(v));
+    }
+
+    pub fn drain(&mut self) -> alloc::vec::Drain<'_, T> {
         self.backing.drain(..)
     }
 
+    /// Returns `true` if the set holds exactly the values of `other`, in any order.
+    ///
+    /// This is not a `PartialEq` impl because `==` between two sets compares their order.
+    pub fn eq_btree_set(&self, other: &BTreeSet<T>) -> bool
+    where
+        T: Ord,
+    {
+        self.len() == other.len() && self.iter().all(|v| other.contains(v))
+    }
+
+    /// Returns `true` if the set holds exactly the values of `other`, in any order.
+    ///
+    /// Like [`eq_btree_set`](Self::eq_btree_set), this is not a `PartialEq` impl because `==`
+    /// between two sets compares their order.
+    pub fn eq_unordered(&self, other: &[T]) -> bool {
+        self.len() == other.len() && self.iter().all(|v| other.contains(v))
+    }
+
     pub fn get<Q>(&self, value: &Q) -> Option<&T>
     where
         T: Borrow<Q>,
@@ -138,10 +297,47 @@ impl<T: Eq> Set<T> {
         self.backing.last().unwrap()
     }
 
+    pub fn get_or_insert_owned<Q>(&mut self, value: &Q) -> &T
+    where
+        T: Borrow<Q>,
+        Q: Eq + ToOwned<Owned = T> + ?Sized,
+    {
+        self.get_or_insert_with(value, Q::to_owned)
+    }
+
     pub fn insert(&mut self, value: T) -> bool {
-        !self.backing.iter().any(|v| *v == value) && {
-            self.backing.push(value);
-            true
+        self.insert_full(value).1
+    }
+
+    /// Adds a value to the set, returning its index and whether it was newly inserted.
+    ///
+    /// If an equal value was already present, the set is unchanged and its index is returned.
+    pub fn insert_full(&mut self, value: T) -> (usize, bool) {
+        match self.position_of(&value) {
+            Some(pos) => (pos, false),
+            None => {
+                self.backing.push(value);
+                (self.backing.len() - 1, true)
+            }
+        }
+    }
+
+    /// Adds a value to a sorted set, keeping it sorted. Returns the value's index and whether it
+    /// was newly inserted.
+    ///
+    /// The set must already be sorted (e.g. by [`sort_and_dedup`](Self::sort_and_dedup) or by
+    /// only ever inserting through this method). Mixing this with plain [`insert`](Self::insert),
+    /// which appends, breaks that invariant.
+    pub fn insert_sorted(&mut self, value: T) -> (usize, bool)
+    where
+        T: Ord,
+    {
+        match self.backing.binary_search(&value) {
+            Ok(pos) => (pos, false),
+            Err(pos) => {
+                self.backing.insert(pos, value);
+                (pos, true)
+            }
         }
     }
 
@@ -152,6 +348,34 @@ impl<T: Eq> Set<T> {
         }
     }
 
+    pub fn into_vec(self) -> Vec<T> {
+        self.backing
+    }
+
+    /// Returns a `Vec` of clones of the set's values, in iteration order, with a capacity of
+    /// exactly `len()`.
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.backing.to_vec()
+    }
+
+    /// Returns a new set with the values in both `self` and `other`.
+    ///
+    /// This is equivalent to `self & other`.
+    pub fn intersection_cloned(&self, other: &Set<T>) -> Set<T>
+    where
+        T: Clone,
+    {
+        self.intersection(other).cloned().collect()
+    }
+
+    /// Removes every value that is not present in `other`, in place.
+    pub fn intersection_with(&mut self, other: &Set<T>) {
+        self.backing.retain(|v| other.contains(v));
+    }
+
     pub fn is_disjoint<'a>(&'a self, other: &'a Self) -> bool {
         self.intersection(other).count() == 0
     }
@@ -164,11 +388,37 @@ impl<T: Eq> Set<T> {
         self.len() <= other.len() && self.difference(other).count() == 0
     }
 
+    /// Like [`is_subset`](Self::is_subset), but sorts both sets' values to check containment in
+    /// O(n log n + m log m) rather than O(n * m).
+    ///
+    /// Small sets fall back to the linear scan, which is faster for them.
+    pub fn sorted_is_subset(&self, other: &Self) -> bool
+    where
+        T: Ord,
+    {
+        const LINEAR_SCAN_MAX_LEN: usize = 16;
+
+        if self.len() > other.len() {
+            return false;
+        }
+        if other.len() <= LINEAR_SCAN_MAX_LEN {
+            return self.is_subset(other);
+        }
+
+        let mut this: Vec<&T> = self.iter().collect();
+        let mut other: Vec<&T> = other.iter().collect();
+        this.sort_unstable();
+        other.sort_unstable();
+
+        let mut other = other.into_iter();
+        this.into_iter().all(|v| other.any(|o| o == v))
+    }
+
     pub fn is_superset(&self, other: &Self) -> bool {
         other.is_subset(self)
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         self.backing.iter()
     }
 
@@ -176,20 +426,87 @@ impl<T: Eq> Set<T> {
         self.backing.len()
     }
 
+    /// Moves the value at index `from` to index `to`, shifting the values in between.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` or `to` is out of bounds.
+    pub fn move_index(&mut self, from: usize, to: usize) {
+        if from < to {
+            self.backing[from..=to].rotate_left(1);
+        } else {
+            self.backing[to..=from].rotate_right(1);
+        }
+    }
+
+    pub fn partition<F>(self, f: F) -> (Self, Self)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        // Values are already unique, so neither half needs to be de-duplicated.
+        let (matching, rest) = self.backing.into_iter().partition(f);
+        (Self { backing: matching }, Self { backing: rest })
+    }
+
+    /// Returns the index of the given value, if it is present.
+    pub fn position_of<Q>(&self, value: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.backing.iter().position(|v| value.eq(v.borrow()))
+    }
+
+    /// Removes every value in `values` from the set, returning how many of them were present.
+    ///
+    /// This makes a single pass over the set, preserving the order of the remaining values.
+    pub fn remove_all<'a, Q, I>(&mut self, values: I) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized + 'a,
+        I: IntoIterator<Item = &'a Q>,
+    {
+        let values: Vec<&Q> = values.into_iter().collect();
+        if values.is_empty() {
+            return 0;
+        }
+
+        let len = self.len();
+        self.backing.retain(|v| !values.contains(&v.borrow()));
+        len - self.len()
+    }
+
+    /// Removes a value from the set, returning whether it was present.
+    ///
+    /// This is equivalent to [`swap_remove`](Self::swap_remove) and does not preserve the order
+    /// of the remaining values. Use [`shift_remove`](Self::shift_remove) if order matters.
     pub fn remove<Q>(&mut self, value: &Q) -> bool
     where
         T: Borrow<Q>,
         Q: Eq + ?Sized,
     {
-        self.take(value).is_some()
+        self.swap_remove(value)
     }
 
     pub fn replace(&mut self, value: T) -> Option<T> {
-        match self.backing.iter_mut().find(|v| **v == value) {
-            Some(v) => Some(core::mem::replace(v, value)),
+        self.replace_full(value).1
+    }
+
+    /// Adds a value to the set, replacing the existing equal value if any, and returns a
+    /// reference to the value now stored.
+    pub fn replace_ref(&mut self, value: T) -> &T {
+        let (pos, _) = self.replace_full(value);
+        &self.backing[pos]
+    }
+
+    /// Adds a value to the set, replacing the existing equal value if any, and returns the
+    /// value's index along with the replaced value.
+    pub fn replace_full(&mut self, value: T) -> (usize, Option<T>) {
+        match self.position_of(&value) {
+            Some(pos) => (pos, Some(core::mem::replace(&mut self.backing[pos], value))),
             None => {
                 self.backing.push(value);
-                None
+                (self.backing.len() - 1, None)
             }
         }
     }
@@ -205,33 +522,224 @@ impl<T: Eq> Set<T> {
         self.backing.retain(f);
     }
 
+    /// Retains only the values for which `f` returns `true`, then shrinks the capacity if less
+    /// than half of it is in use.
+    ///
+    /// Plain [`retain`](Self::retain) never releases memory, which can waste a lot of it in a
+    /// long-lived set after most of its values have been removed.
+    pub fn retain_and_shrink<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain(f);
+        if self.len() < self.capacity() / 2 {
+            self.shrink_to_fit();
+        }
+    }
+
+    /// Retains only the values for which `f` returns `true`, like [`retain`](Self::retain), but
+    /// removes each rejected value by swapping the last value into its place.
+    ///
+    /// Both are a single O(n) pass, but `retain` moves every kept value that follows a removed
+    /// one, while this moves only one value per removal. That is cheaper when few values are
+    /// removed, at the cost of not preserving the order of the remaining values.
+    pub fn retain_swap<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut i = 0;
+        while i < self.backing.len() {
+            if f(&self.backing[i]) {
+                i += 1;
+            } else {
+                self.backing.swap_remove(i);
+            }
+        }
+    }
+
     pub fn shrink_to_fit(&mut self) {
         self.backing.shrink_to_fit()
     }
 
+    /// Returns the number of values in exactly one of `self` and `other`, without building the
+    /// symmetric difference.
+    pub fn symmetric_difference_count(&self, other: &Set<T>) -> usize {
+        self.len() + other.len() - 2 * self.common_count(other)
+    }
+
     pub fn symmetric_difference<'a>(&'a self, other: &'a Self) -> SymmetricDifference<'a, T> {
         SymmetricDifference {
             iter: self.difference(other).chain(other.difference(self)),
         }
     }
 
+    /// Returns a new set with the values in exactly one of `self` and `other`.
+    ///
+    /// This is equivalent to `self ^ other`.
+    pub fn symmetric_difference_cloned(&self, other: &Set<T>) -> Set<T>
+    where
+        T: Clone,
+    {
+        self.symmetric_difference(other).cloned().collect()
+    }
+
+    /// Replaces the set's contents with the values present in exactly one of `self` and `other`,
+    /// in place.
+    pub fn symmetric_difference_with(&mut self, other: &Set<T>)
+    where
+        T: Clone,
+    {
+        // Find `other`'s additions before removing anything so they are judged against the
+        // original contents of `self`.
+        let missing: Vec<T> = other.difference(self).cloned().collect();
+        self.difference_with(other);
+        self.backing.extend(missing);
+    }
+
+    /// Removes and returns the value in the set equal to `value`, if any.
+    ///
+    /// This is equivalent to [`swap_take`](Self::swap_take) and does not preserve the order of
+    /// the remaining values.
     pub fn take<Q>(&mut self, value: &Q) -> Option<T>
     where
         T: Borrow<Q>,
         Q: Eq + ?Sized,
     {
-        self.backing
-            .iter()
-            .position(|v| value.eq(v.borrow()))
+        self.swap_take(value)
+    }
+
+    /// Sorts the values and removes any duplicates.
+    ///
+    /// The sort is stable, so of several equal values, the one that came first in the set's
+    /// previous order is kept.
+    pub fn sort_and_dedup(&mut self)
+    where
+        T: Ord,
+    {
+        self.backing.sort();
+        self.backing.dedup();
+    }
+
+    /// Removes a value from the set by swapping it with the last value, returning whether it was
+    /// present.
+    ///
+    /// This is O(1) once the value is found, but does not preserve the order of the remaining
+    /// values.
+    pub fn swap_remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.swap_take(value).is_some()
+    }
+
+    /// Removes and returns the value in the set equal to `value`, if any, by swapping it with the
+    /// last value.
+    ///
+    /// This is O(1) once the value is found, but does not preserve the order of the remaining
+    /// values.
+    pub fn swap_take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position_of(value)
             .map(|pos| self.backing.swap_remove(pos))
     }
 
+    /// Removes a value from the set by shifting all following values down by one, returning
+    /// whether it was present.
+    ///
+    /// This preserves the order of the remaining values, but is O(n).
+    pub fn shift_remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.shift_take(value).is_some()
+    }
+
+    /// Removes and returns the value in the set equal to `value`, if any, by shifting all
+    /// following values down by one.
+    ///
+    /// This preserves the order of the remaining values, but is O(n).
+    pub fn shift_take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position_of(value).map(|pos| self.backing.remove(pos))
+    }
+
+    pub fn take_if<Q, F>(&mut self, value: &Q, f: F) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+        F: FnOnce(&T) -> bool,
+    {
+        let pos = self.position_of(value)?;
+        if f(&self.backing[pos]) {
+            Some(self.backing.swap_remove(pos))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of values in either `self` or `other`, without building the union.
+    pub fn union_count(&self, other: &Set<T>) -> usize {
+        self.len() + other.len() - self.common_count(other)
+    }
+
+    /// Returns the number of values in both `self` and `other`, scanning the smaller set.
+    fn common_count(&self, other: &Set<T>) -> usize {
+        let (small, large) = if self.len() <= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        small.intersection(large).count()
+    }
+
     pub fn union<'a>(&'a self, other: &'a Self) -> Union<'a, T> {
         Union {
             iter: self.iter().chain(other.difference(self)),
         }
     }
 
+    /// Returns a new set with the values in either `self` or `other`.
+    ///
+    /// This is equivalent to `self | other`.
+    pub fn union_cloned(&self, other: &Set<T>) -> Set<T>
+    where
+        T: Clone,
+    {
+        self.union(other).cloned().collect()
+    }
+
+    /// Inserts every value of `other` that is not already present, in place.
+    pub fn union_with(&mut self, other: &Set<T>)
+    where
+        T: Clone,
+    {
+        let missing: Vec<T> = other.difference(self).cloned().collect();
+        self.backing.extend(missing);
+    }
+
+    /// Removes the value if it is present, or inserts it if it is absent, returning whether the
+    /// value is now in the set.
+    pub fn toggle(&mut self, value: T) -> bool {
+        match self.position_of(&value) {
+            Some(pos) => {
+                self.backing.swap_remove(pos);
+                false
+            }
+            None => {
+                self.backing.push(value);
+                true
+            }
+        }
+    }
+
     pub fn try_reserve(
         &mut self,
         additional: usize,
@@ -239,6 +747,17 @@ impl<T: Eq> Set<T> {
         self.backing.try_reserve(additional)
     }
 
+    pub fn try_reserve_exact(
+        &mut self,
+        additional: usize,
+    ) -> Result<(), alloc::collections::TryReserveError> {
+        self.backing.try_reserve_exact(additional)
+    }
+
+    /// Shrinks the capacity of the set with a lower bound.
+    ///
+    /// The capacity will remain at least as large as both the length and `min_capacity`, so
+    /// passing a `min_capacity` below the length is fine.
     pub fn shrink_to(&mut self, min_capacity: usize) {
         self.backing.shrink_to(min_capacity)
     }
@@ -305,18 +824,42 @@ impl<T: Eq> FromIterator<T> for Set<T> {
 
 impl<T: Eq> Extend<T> for Set<T> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
         for item in iter {
             self.insert(item);
         }
     }
+
+    #[cfg(feature = "nightly")]
+    fn extend_one(&mut self, item: T) {
+        self.insert(item);
+    }
+
+    #[cfg(feature = "nightly")]
+    fn extend_reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
 }
 
 impl<'a, T: 'a + Copy + Eq> Extend<&'a T> for Set<T> {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
         for item in iter {
             self.insert(*item);
         }
     }
+
+    #[cfg(feature = "nightly")]
+    fn extend_one(&mut self, item: &'a T) {
+        self.insert(*item);
+    }
+
+    #[cfg(feature = "nightly")]
+    fn extend_reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
 }
 
 impl<V: Eq, T: Into<Vec<V>>> From<T> for Set<V> {
@@ -329,31 +872,37 @@ impl<V: Eq, T: Into<Vec<V>>> From<T> for Set<V> {
     }
 }
 
+impl<T: Ord> From<Set<T>> for BTreeSet<T> {
+    fn from(set: Set<T>) -> Self {
+        set.backing.into_iter().collect()
+    }
+}
+
 impl<T: Clone + Eq> core::ops::BitOr<&Set<T>> for &Set<T> {
     type Output = Set<T>;
     fn bitor(self, rhs: &Set<T>) -> Set<T> {
-        self.union(rhs).cloned().collect()
+        self.union_cloned(rhs)
     }
 }
 
 impl<T: Clone + Eq> core::ops::BitAnd<&Set<T>> for &Set<T> {
     type Output = Set<T>;
     fn bitand(self, rhs: &Set<T>) -> Set<T> {
-        self.intersection(rhs).cloned().collect()
+        self.intersection_cloned(rhs)
     }
 }
 
 impl<T: Clone + Eq> core::ops::BitXor<&Set<T>> for &Set<T> {
     type Output = Set<T>;
     fn bitxor(self, rhs: &Set<T>) -> Set<T> {
-        self.symmetric_difference(rhs).cloned().collect()
+        self.symmetric_difference_cloned(rhs)
     }
 }
 
 impl<T: Clone + Eq> core::ops::Sub<&Set<T>> for &Set<T> {
     type Output = Set<T>;
     fn sub(self, rhs: &Set<T>) -> Set<T> {
-        self.difference(rhs).cloned().collect()
+        self.difference_cloned(rhs)
     }
 }
 
@@ -379,8 +928,9 @@ where
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (_, upper) = self.iter.size_hint();
-        (0, upper)
+        // The values are distinct, so at most `other.len()` of them can be in `other`.
+        let (len, upper) = self.iter.size_hint();
+        (len.saturating_sub(self.other.len()), upper)
     }
 }
 
@@ -423,7 +973,7 @@ where
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (_, upper) = self.iter.size_hint();
-        (0, upper)
+        (0, upper.map(|upper| upper.min(self.other.len())))
     }
 }
 
@@ -459,8 +1009,7 @@ where
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (_, upper) = self.iter.size_hint();
-        (0, upper)
+        self.iter.size_hint()
     }
 }
 
@@ -491,8 +1040,7 @@ where
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (_, upper) = self.iter.size_hint();
-        (0, upper)
+        self.iter.size_hint()
     }
 }
 
@@ -507,6 +1055,124 @@ where
 
 impl<T> FusedIterator for Union<'_, T> where T: Eq {}
 
+/// A cursor over a [`Set`] that can move back and forth and edit the set in place.
+///
+/// The cursor points either at a value or at a "ghost" position between the last and the first
+/// value. Moving past either end of the set lands on the ghost position, and moving again wraps
+/// around to the other end. It is created with [`Set::cursor_mut`].
+pub struct CursorMut<'a, T: 'a> {
+    index: usize,
+    backing: &'a mut Vec<T>,
+}
+
+impl<'a, T: 'a> CursorMut<'a, T> {
+    fn next_index(&self) -> usize {
+        if self.index < self.backing.len() {
+            self.index + 1
+        } else {
+            0
+        }
+    }
+
+    fn prev_index(&self) -> usize {
+        if self.index == 0 {
+            self.backing.len()
+        } else {
+            self.index - 1
+        }
+    }
+
+    /// Returns the index of the current value, or `None` at the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        (self.index < self.backing.len()).then(|| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        self.index = self.next_index();
+    }
+
+    pub fn move_prev(&mut self) {
+        self.index = self.prev_index();
+    }
+
+    pub fn current(&self) -> Option<&T> {
+        self.backing.get(self.index)
+    }
+
+    pub fn peek_next(&self) -> Option<&T> {
+        self.backing.get(self.next_index())
+    }
+
+    pub fn peek_prev(&self) -> Option<&T> {
+        self.backing.get(self.prev_index())
+    }
+
+    /// Removes the current value, returning it.
+    ///
+    /// This is equivalent to [`swap_remove_current`](Self::swap_remove_current) and does not
+    /// preserve the order of the remaining values.
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.swap_remove_current()
+    }
+
+    /// Removes the current value by swapping it with the last value, returning it.
+    ///
+    /// The cursor is left pointing at the value that was swapped into place, or at the ghost
+    /// position if the current value was the last one.
+    pub fn swap_remove_current(&mut self) -> Option<T> {
+        (self.index < self.backing.len()).then(|| self.backing.swap_remove(self.index))
+    }
+
+    /// Removes the current value by shifting all following values down by one, returning it.
+    ///
+    /// The cursor is left pointing at the value that followed the removed one.
+    pub fn shift_remove_current(&mut self) -> Option<T> {
+        (self.index < self.backing.len()).then(|| self.backing.remove(self.index))
+    }
+}
+
+impl<'a, T: 'a + Eq> CursorMut<'a, T> {
+    /// Inserts a value after the current one, or at the front of the set if the cursor is at the
+    /// ghost position. The cursor does not move.
+    ///
+    /// Returns whether the value was newly inserted. If an equal value was already present, the
+    /// set is unchanged, as with [`Set::insert`].
+    pub fn insert_after(&mut self, value: T) -> bool {
+        if self.backing.contains(&value) {
+            return false;
+        }
+        let index = self.next_index();
+        self.backing.insert(index, value);
+        if index <= self.index {
+            self.index += 1;
+        }
+        true
+    }
+}
+
+/// The error returned by [`Set::try_from_vec`] when a value appears more than once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DuplicateValue {
+    /// The index of the first occurrence of the repeated value.
+    pub first: usize,
+    /// The index of the value that repeats it.
+    pub index: usize,
+}
+
+impl fmt::Display for DuplicateValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "duplicate value at index {} (first seen at index {})",
+            self.index, self.first
+        )
+    }
+}
+
+#[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+impl std::error::Error for DuplicateValue {}
+
 #[cfg(feature = "serde")]
 mod set_serde {
     use core::{fmt, marker::PhantomData};
@@ -621,15 +1287,82 @@ mod set_serde {
     }
 }
 
-// taken from libstd/collections/hash/set.rs @ 7454b2
-#[cfg(test)]
-mod test_set {
-    use pretty_assertions::assert_eq;
+#[cfg(feature = "rayon")]
+mod set_rayon {
+    use alloc::vec::Vec;
+
+    use rayon::prelude::*;
 
     use super::Set;
 
-    #[test]
-    fn test_zero_capacities() {
+    /// Collects the items of the parallel iterator into a `Vec` and checks whether the set contains
+    /// each of them, both in parallel. The new values are then appended in one sequential pass,
+    /// so as with [`Extend`], the first of several equal values is kept.
+    ///
+    /// The sequential pass only compares new values with each other; the scan of the existing
+    /// values is what runs in parallel.
+    #[cfg_attr(any(docsrs, feature = "nightly"), doc(cfg(feature = "rayon")))]
+    impl<T: Eq + Send + Sync> ParallelExtend<T> for Set<T> {
+        fn par_extend<I>(&mut self, par_iter: I)
+        where
+            I: IntoParallelIterator<Item = T>,
+        {
+            let values: Vec<T> = par_iter.into_par_iter().collect();
+            let present: Vec<bool> = values.par_iter().map(|v| self.contains(v)).collect();
+
+            let len = self.len();
+            for (v, present) in values.into_iter().zip(present) {
+                if !present && !self.backing[len..].contains(&v) {
+                    self.backing.push(v);
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use pretty_assertions::assert_eq;
+        use rayon::prelude::*;
+
+        use super::Set;
+
+        #[test]
+        fn test_par_extend() {
+            let mut s: Set<u64> = (0..100).collect();
+
+            s.par_extend((0..3000).into_par_iter().map(|i| i % 1000));
+
+            assert_eq!(s.len(), 1000);
+            assert!((0..1000).all(|i| s.contains(&i)));
+            assert_eq!(s.iter().take(3).copied().collect::<Vec<_>>(), [0, 1, 2]);
+        }
+    }
+}
+
+// taken from libstd/collections/hash/set.rs @ 7454b2
+#[cfg(test)]
+mod test_set {
+    use alloc::collections::BTreeSet;
+
+    use pretty_assertions::assert_eq;
+    use rand::{thread_rng, Rng};
+
+    use super::{DuplicateValue, Set};
+
+    /// Compares equal on its first field only, so tests can tell which of two equal values is kept.
+    #[derive(Debug)]
+    struct Foo(&'static str, i32);
+
+    impl PartialEq for Foo {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Foo {}
+
+    #[test]
+    fn test_zero_capacities() {
         type S = Set<i32>;
 
         let s = S::new();
@@ -654,6 +1387,136 @@ mod test_set {
         assert_eq!(s.capacity(), 0);
     }
 
+    #[test]
+    fn test_const_new() {
+        static EMPTY: Set<&str> = Set::new();
+
+        assert_eq!(EMPTY.len(), 0);
+        assert!(EMPTY.is_empty());
+    }
+
+    #[test]
+    fn test_any_all() {
+        let s = Set::from([1, 2, 3]);
+
+        assert!(s.any(|&v| v == 2));
+        assert!(!s.any(|&v| v > 3));
+        assert!(s.all(|&v| v > 0));
+        assert!(!s.all(|&v| v < 3));
+
+        let empty: Set<i32> = Set::new();
+        assert!(!empty.any(|_| true));
+        assert!(empty.all(|_| false));
+    }
+
+    #[test]
+    fn test_as_slice() {
+        let s = Set::from([1, 2, 2, 3]);
+        assert_eq!(s.as_slice(), [1, 2, 3]);
+        assert!(Set::<i32>::new().as_slice().is_empty());
+    }
+
+    #[test]
+    fn test_into_vec() {
+        let s = Set::from([3, 1, 2, 1]);
+        assert_eq!(s.into_vec(), [3, 1, 2]);
+    }
+
+    #[test]
+    fn test_chunks() {
+        let s: Set<i32> = (1..=7).collect();
+
+        let chunks: Vec<_> = s.chunks(3).collect();
+        assert_eq!(
+            chunks.iter().map(|c| c.len()).collect::<Vec<_>>(),
+            [3, 3, 1]
+        );
+        assert_eq!(chunks[2], [7]);
+        assert_eq!(chunks.concat(), s.as_slice());
+    }
+
+    #[test]
+    fn test_to_vec() {
+        let mut s = Set::with_capacity(10);
+        s.extend([3, 1, 2]);
+
+        let v = s.to_vec();
+        assert_eq!(v, [3, 1, 2]);
+        assert_eq!(v.capacity(), 3);
+        assert_eq!(s.len(), 3);
+    }
+
+    #[test]
+    fn test_from_vec_unchecked() {
+        // SAFETY: The values are unique.
+        let s = unsafe { Set::from_vec_unchecked(vec![3, 1, 2]) };
+        assert_eq!(s.len(), 3);
+        assert!(s.contains(&1));
+        assert_eq!(s, Set::from([3, 1, 2]));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Set contains duplicate values at indices 0 and 2")]
+    fn test_from_vec_unchecked_duplicates() {
+        // SAFETY: Not safe! Debug builds must catch this.
+        let _ = unsafe { Set::from_vec_unchecked(vec![3, 1, 3]) };
+    }
+
+    #[test]
+    fn test_binary_search() {
+        let s = Set::from([1, 3, 5, 7]);
+
+        assert_eq!(s.binary_search(&1), Ok(0));
+        assert_eq!(s.binary_search(&7), Ok(3));
+        assert_eq!(s.binary_search(&0), Err(0));
+        assert_eq!(s.binary_search(&4), Err(2));
+        assert_eq!(s.binary_search(&8), Err(4));
+
+        let s = Set::from(["a".to_string(), "c".to_string()]);
+        assert_eq!(s.binary_search("c"), Ok(1));
+        assert_eq!(s.binary_search("b"), Err(1));
+    }
+
+    #[test]
+    fn test_get_or_insert_owned() {
+        let mut s: Set<String> = Set::new();
+
+        let ptr = s.get_or_insert_owned("hello").as_ptr();
+        assert_eq!(s.len(), 1);
+
+        // The existing element is returned rather than a fresh allocation.
+        assert_eq!(s.get_or_insert_owned("hello").as_ptr(), ptr);
+        assert_eq!(s.len(), 1);
+
+        assert_eq!(s.get_or_insert_owned("world"), "world");
+        assert_eq!(s.len(), 2);
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        let mut s = Set::new();
+        for x in [7, 2, 9, 4, 2, 0, 7] {
+            s.insert_sorted(x);
+        }
+        assert_eq!(s.as_slice(), [0, 2, 4, 7, 9]);
+
+        assert_eq!(s.insert_sorted(4), (2, false));
+        assert_eq!(s.insert_sorted(5), (3, true));
+        assert_eq!(s.binary_search(&9), Ok(5));
+    }
+
+    #[test]
+    fn test_insert_full() {
+        let mut s = Set::new();
+
+        assert_eq!(s.insert_full('a'), (0, true));
+        assert_eq!(s.insert_full('b'), (1, true));
+        assert_eq!(s.insert_full('a'), (0, false));
+        assert_eq!(s.insert_full('b'), (1, false));
+        assert_eq!(s.len(), 2);
+    }
+
     #[test]
     fn test_disjoint() {
         let mut xs = Set::new();
@@ -705,6 +1568,24 @@ mod test_set {
         assert!(b.is_superset(&a));
     }
 
+    #[test]
+    fn test_sorted_is_subset() {
+        let mut rng = thread_rng();
+
+        for _ in 0..200 {
+            let a: Set<i32> = (0..rng.gen_range(0..40))
+                .map(|_| rng.gen_range(0..50))
+                .collect();
+            let b: Set<i32> = (0..rng.gen_range(0..60))
+                .map(|_| rng.gen_range(0..50))
+                .collect();
+
+            assert_eq!(a.sorted_is_subset(&b), a.is_subset(&b));
+            assert_eq!(b.sorted_is_subset(&a), b.is_subset(&a));
+            assert!(a.sorted_is_subset(&(&a | &b)));
+        }
+    }
+
     #[test]
     fn test_iterate() {
         let mut a = Set::new();
@@ -800,6 +1681,69 @@ mod test_set {
         assert_eq!(i, expected.len());
     }
 
+    #[test]
+    fn test_cursor_mut_walk() {
+        let mut s = Set::from([1, 2, 3]);
+        let mut cursor = s.cursor_mut();
+
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&1));
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(cursor.peek_next(), Some(&2));
+
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_next(), Some(&1));
+        assert_eq!(cursor.peek_prev(), Some(&3));
+
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&3));
+    }
+
+    #[test]
+    fn test_cursor_mut_remove() {
+        let mut s: Set<i32> = (0..6).collect();
+        let mut cursor = s.cursor_mut();
+
+        while let Some(v) = cursor.current() {
+            if v % 2 == 0 {
+                cursor.shift_remove_current();
+            } else {
+                cursor.move_next();
+            }
+        }
+        assert_eq!(s.as_slice(), [1, 3, 5]);
+
+        let mut cursor = s.cursor_mut();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.current(), Some(&5));
+        cursor.move_next();
+        assert_eq!(cursor.swap_remove_current(), Some(3));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+
+        assert_eq!(s.as_slice(), [5]);
+    }
+
+    #[test]
+    fn test_cursor_mut_insert_after() {
+        let mut s = Set::from([1, 3]);
+        let mut cursor = s.cursor_mut();
+
+        assert!(cursor.insert_after(2));
+        assert!(!cursor.insert_after(3));
+        assert_eq!(cursor.current(), Some(&1));
+
+        cursor.move_prev();
+        assert!(cursor.insert_after(0));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_next(), Some(&0));
+
+        assert_eq!(s.as_slice(), [0, 1, 2, 3]);
+    }
+
     #[test]
     fn test_difference() {
         let mut a = Set::new();
@@ -896,6 +1840,152 @@ mod test_set {
         assert_eq!(i, expected.len());
     }
 
+    #[test]
+    fn test_set_ops_size_hint() {
+        let a = Set::from([1, 2, 3, 4, 5]);
+        let b = Set::from([4, 5, 6]);
+
+        let mut union = a.union(&b);
+        assert_eq!(union.size_hint(), (5, Some(8)));
+        union.next();
+        assert_eq!(union.size_hint(), (4, Some(7)));
+        assert_eq!(union.count(), 5);
+
+        assert_eq!(a.difference(&b).size_hint(), (2, Some(5)));
+        assert_eq!(b.difference(&a).size_hint(), (0, Some(3)));
+
+        assert_eq!(a.intersection(&b).size_hint(), (0, Some(3)));
+        assert_eq!(b.intersection(&a).size_hint(), (0, Some(3)));
+
+        assert_eq!(a.symmetric_difference(&b).size_hint(), (2, Some(8)));
+
+        for (x, y) in [(&a, &b), (&b, &a)] {
+            for (hint, len) in [
+                (x.union(y).size_hint(), x.union(y).count()),
+                (x.difference(y).size_hint(), x.difference(y).count()),
+                (x.intersection(y).size_hint(), x.intersection(y).count()),
+                (
+                    x.symmetric_difference(y).size_hint(),
+                    x.symmetric_difference(y).count(),
+                ),
+            ] {
+                assert!(hint.0 <= len && len <= hint.1.unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_op_counts() {
+        let a = Set::from([1, 2, 3, 4, 5]);
+        let b = Set::from([4, 5, 6]);
+        let empty = Set::new();
+
+        for (x, y) in [(&a, &b), (&b, &a), (&a, &a), (&a, &empty), (&empty, &empty)] {
+            assert_eq!(x.union_count(y), x.union_cloned(y).len());
+            assert_eq!(
+                x.symmetric_difference_count(y),
+                x.symmetric_difference_cloned(y).len()
+            );
+        }
+
+        assert_eq!(a.union_count(&b), 6);
+        assert_eq!(a.symmetric_difference_count(&b), 4);
+    }
+
+    #[test]
+    fn test_union_with() {
+        let a = Set::from([1, 3, 5, 7]);
+        let b = Set::from([2, 3, 4, 7, 9]);
+
+        let mut c = a.clone();
+        c.union_with(&b);
+        assert_eq!(c, &a | &b);
+
+        let mut c = b.clone();
+        c.union_with(&a);
+        assert_eq!(c, &b | &a);
+    }
+
+    #[test]
+    fn test_intersection_with() {
+        let a = Set::from([1, 3, 5, 7]);
+        let b = Set::from([2, 3, 4, 7, 9]);
+
+        let mut c = a.clone();
+        c.intersection_with(&b);
+        assert_eq!(c, &a & &b);
+
+        let mut c = b.clone();
+        c.intersection_with(&a);
+        assert_eq!(c, &b & &a);
+    }
+
+    #[test]
+    fn test_difference_with() {
+        let a = Set::from([1, 3, 5, 7]);
+        let b = Set::from([2, 3, 4, 7, 9]);
+
+        let mut c = a.clone();
+        c.difference_with(&b);
+        assert_eq!(c, &a - &b);
+
+        let mut c = b.clone();
+        c.difference_with(&a);
+        assert_eq!(c, &b - &a);
+    }
+
+    #[test]
+    fn test_symmetric_difference_with() {
+        let a = Set::from([1, 3, 5, 7]);
+        let b = Set::from([2, 3, 4, 7, 9]);
+
+        let mut c = a.clone();
+        c.symmetric_difference_with(&b);
+        assert_eq!(c, &a ^ &b);
+
+        let mut c = b.clone();
+        c.symmetric_difference_with(&a);
+        assert_eq!(c, &b ^ &a);
+
+        let mut c = a.clone();
+        c.symmetric_difference_with(&a);
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn test_cloned_set_ops() {
+        let a = Set::from([1, 3, 5, 7]);
+        let b = Set::from([2, 3, 4, 7, 9]);
+
+        assert_eq!(a.union_cloned(&b), &a | &b);
+        assert_eq!(a.union_cloned(&b), Set::from([1, 3, 5, 7, 2, 4, 9]));
+
+        assert_eq!(a.intersection_cloned(&b), &a & &b);
+        assert_eq!(a.intersection_cloned(&b), Set::from([3, 7]));
+
+        assert_eq!(a.difference_cloned(&b), &a - &b);
+        assert_eq!(a.difference_cloned(&b), Set::from([1, 5]));
+
+        assert_eq!(a.symmetric_difference_cloned(&b), &a ^ &b);
+        assert_eq!(
+            a.symmetric_difference_cloned(&b),
+            Set::from([1, 5, 2, 4, 9])
+        );
+    }
+
+    #[test]
+    fn test_try_from_vec() {
+        let s = Set::try_from_vec(vec![3, 1, 2]).unwrap();
+        assert_eq!(s.as_slice(), [3, 1, 2]);
+
+        let err = Set::try_from_vec(vec![3, 1, 2, 1]).unwrap_err();
+        assert_eq!(err, DuplicateValue { first: 1, index: 3 });
+        assert_eq!(
+            err.to_string(),
+            "duplicate value at index 3 (first seen at index 1)"
+        );
+    }
+
     #[test]
     fn test_from_iter() {
         let xs = [1, 2, 2, 3, 4, 5, 6, 7, 8, 9];
@@ -911,6 +2001,20 @@ mod test_set {
         assert_eq!(set.iter().len(), xs.len() - 1);
     }
 
+    #[test]
+    fn test_from_copied() {
+        let xs = [1, 2, 2, 3];
+
+        let set = Set::from_copied(&xs);
+        assert_eq!(set, Set::from([1, 2, 3]));
+
+        let copy = Set::from_copied(&set);
+        assert_eq!(copy, set);
+
+        let refs: Set<_> = xs.iter().collect();
+        assert!(refs.contains(&&2));
+    }
+
     #[test]
     fn test_move_iter() {
         let hs = {
@@ -999,6 +2103,20 @@ mod test_set {
         }
     }
 
+    #[test]
+    fn test_from_iter_last() {
+        let items = || [Foo("a", 1), Foo("b", 2), Foo("a", 3)];
+
+        let first: Set<Foo> = items().into_iter().collect();
+        assert_eq!(first.len(), 2);
+        assert_eq!(first.get(&Foo("a", 0)).unwrap().1, 1);
+
+        let last = Set::from_iter_last(items());
+        assert_eq!(last.len(), 2);
+        assert_eq!(last.get(&Foo("a", 0)).unwrap().1, 3);
+        assert_eq!(last.iter().map(|f| f.0).collect::<Vec<_>>(), ["a", "b"]);
+    }
+
     #[test]
     fn test_replace() {
         #[derive(Debug)]
@@ -1040,6 +2158,174 @@ mod test_set {
         assert_eq!(it.next(), None, "Should be no more items in the iterator");
     }
 
+    #[test]
+    fn test_move_index() {
+        let mut s = Set::from(['a', 'b', 'c', 'd']);
+
+        s.move_index(3, 0);
+        assert_eq!(s.as_slice(), ['d', 'a', 'b', 'c']);
+
+        s.move_index(1, 2);
+        assert_eq!(s.as_slice(), ['d', 'b', 'a', 'c']);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_move_index_out_of_bounds() {
+        let mut s = Set::from(['a', 'b', 'c', 'd']);
+        s.move_index(4, 0);
+    }
+
+    #[test]
+    fn test_partition() {
+        let s = Set::from([1, 2, 3, 4, 5, 6]);
+
+        let (even, odd) = s.partition(|v| v % 2 == 0);
+        assert_eq!(even, Set::from([2, 4, 6]));
+        assert_eq!(odd, Set::from([1, 3, 5]));
+    }
+
+    #[test]
+    fn test_position_of() {
+        let s = Set::from(["a".to_string(), "b".to_string(), "c".to_string()]);
+
+        assert_eq!(s.position_of("a"), Some(0));
+        assert_eq!(s.position_of("c"), Some(2));
+        assert_eq!(s.position_of("d"), None);
+    }
+
+    #[test]
+    fn test_remove_all() {
+        let mut s: Set<_> = (0..5).collect();
+
+        assert_eq!(s.remove_all(&[1, 3, 7, 3]), 2);
+        assert_eq!(s.as_slice(), [0, 2, 4]);
+
+        assert_eq!(s.remove_all(&[]), 0);
+        assert_eq!(s.len(), 3);
+
+        let mut s = Set::from(["a".to_string(), "b".to_string()]);
+        assert_eq!(s.remove_all(["b", "c"]), 1);
+        assert_eq!(s.as_slice(), ["a"]);
+    }
+
+    #[test]
+    fn test_sort_and_dedup() {
+        let mut s = Set {
+            backing: vec![3, 1, 3, 2, 1, 1],
+        };
+
+        s.sort_and_dedup();
+        assert_eq!(s.as_slice(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_swap_remove_order() {
+        let mut s: Set<_> = (0..5).collect();
+
+        assert!(s.remove(&0));
+        assert_eq!(s.iter().copied().collect::<Vec<_>>(), [4, 1, 2, 3]);
+
+        assert!(s.swap_remove(&1));
+        assert_eq!(s.iter().copied().collect::<Vec<_>>(), [4, 3, 2]);
+
+        assert_eq!(s.take(&4), Some(4));
+        assert_eq!(s.iter().copied().collect::<Vec<_>>(), [2, 3]);
+
+        assert_eq!(s.swap_take(&2), Some(2));
+        assert_eq!(s.iter().copied().collect::<Vec<_>>(), [3]);
+
+        assert!(!s.swap_remove(&2));
+        assert_eq!(s.swap_take(&2), None);
+    }
+
+    #[test]
+    fn test_shift_remove_order() {
+        let mut s: Set<_> = (0..5).collect();
+
+        assert!(s.shift_remove(&0));
+        assert_eq!(s.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+
+        assert!(s.shift_remove(&2));
+        assert_eq!(s.iter().copied().collect::<Vec<_>>(), [1, 3, 4]);
+
+        assert!(!s.shift_remove(&2));
+
+        assert_eq!(s.shift_take(&1), Some(1));
+        assert_eq!(s.iter().copied().collect::<Vec<_>>(), [3, 4]);
+
+        assert_eq!(s.shift_take(&1), None);
+    }
+
+    #[test]
+    fn test_take_if() {
+        let mut s = Set::new();
+        s.insert(Foo("a", 1));
+        s.insert(Foo("b", 2));
+
+        assert_eq!(s.take_if(&Foo("a", 0), |foo| foo.1 == 2), None);
+        assert_eq!(s.len(), 2);
+
+        let Foo(a, b) = s
+            .take_if(&Foo("a", 0), |foo| foo.1 == 1)
+            .expect("Predicate should have matched");
+        assert_eq!(a, "a");
+        assert_eq!(b, 1);
+        assert_eq!(s.len(), 1);
+
+        assert_eq!(s.take_if(&Foo("c", 0), |_| true), None);
+    }
+
+    #[test]
+    fn test_replace_full() {
+        let mut s = Set::new();
+        assert_eq!(s.replace_full(Foo("a", 1)), (0, None));
+        assert_eq!(s.replace_full(Foo("b", 2)), (1, None));
+
+        let (index, existing) = s.replace_full(Foo("b", 3));
+        assert_eq!(index, 1);
+
+        // Do _not_ use `assert_eq!()` here as the `PartialEq` implementation
+        // only checks `Foo.0`, but we also need to check `Foo.1`.
+        let Foo(b, n) = existing.expect("Did not get the existing item");
+        assert_eq!(b, "b");
+        assert_eq!(n, 2);
+
+        let &Foo(b, n) = s.get(&Foo("b", 0)).unwrap();
+        assert_eq!(b, "b");
+        assert_eq!(n, 3);
+        assert_eq!(s.len(), 2);
+    }
+
+    #[test]
+    fn test_replace_ref() {
+        let mut s = Set::new();
+
+        let &Foo(a, n) = s.replace_ref(Foo("a", 1));
+        assert_eq!(a, "a");
+        assert_eq!(n, 1);
+
+        let &Foo(a, n) = s.replace_ref(Foo("a", 2));
+        assert_eq!(a, "a");
+        assert_eq!(n, 2);
+
+        assert_eq!(s.len(), 1);
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut s = Set::from([1, 2]);
+
+        assert!(s.toggle(3));
+        assert!(s.contains(&3));
+
+        assert!(!s.toggle(3));
+        assert!(!s.contains(&3));
+
+        assert!(!s.toggle(1));
+        assert_eq!(s.as_slice(), [2]);
+    }
+
     #[test]
     fn test_extend_ref() {
         let mut a = Set::new();
@@ -1068,6 +2354,63 @@ mod test_set {
         assert!(a.contains(&6));
     }
 
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn test_extend_one() {
+        let mut a = Set::new();
+        Extend::<i32>::extend_reserve(&mut a, 3);
+        assert!(a.capacity() >= 3);
+
+        a.extend_one(1);
+        a.extend_one(2);
+        a.extend_one(1);
+        assert_eq!(a.as_slice(), [1, 2]);
+
+        a.extend_one(&3);
+        assert!(a.contains(&3));
+    }
+
+    #[test]
+    fn test_extend_reserves() {
+        let mut a = Set::new();
+        a.extend(0..1000);
+        assert_eq!(a.len(), 1000);
+        assert_eq!(a.capacity(), 1000);
+
+        let mut b: Set<i32> = Set::new();
+        b.extend(a.iter());
+        assert_eq!(b.len(), 1000);
+        assert_eq!(b.capacity(), 1000);
+    }
+
+    #[test]
+    fn test_shrink_to() {
+        let mut set: Set<i32> = Set::with_capacity(100);
+        set.extend(0..10);
+
+        set.shrink_to(20);
+        assert!(set.capacity() >= 20);
+
+        set.shrink_to(0);
+        assert!(set.capacity() >= set.len());
+        assert_eq!(set.len(), 10);
+        assert!(set.contains(&9));
+    }
+
+    #[test]
+    fn test_retain_and_shrink() {
+        let mut set: Set<i32> = (0..100).collect();
+        assert_eq!(set.capacity(), 100);
+
+        set.retain_and_shrink(|&k| k >= 60);
+        assert_eq!(set.len(), 40);
+        assert_eq!(set.capacity(), 40);
+
+        set.retain_and_shrink(|&k| k >= 70);
+        assert_eq!(set.len(), 30);
+        assert_eq!(set.capacity(), 40);
+    }
+
     #[test]
     fn test_retain() {
         let xs = [1, 2, 3, 4, 5, 6];
@@ -1079,6 +2422,70 @@ mod test_set {
         assert!(set.contains(&6));
     }
 
+    #[test]
+    fn test_retain_swap() {
+        let mut set: Set<i32> = (1..=10).collect();
+        set.retain_swap(|&k| k % 3 != 0);
+
+        let mut kept = set.into_vec();
+        kept.sort_unstable();
+        assert_eq!(kept, [1, 2, 4, 5, 7, 8, 10]);
+
+        let mut set: Set<i32> = (1..=4).collect();
+        set.retain_swap(|_| false);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_try_reserve_exact() {
+        let mut empty_bytes: Set<u8> = Set::new();
+
+        empty_bytes
+            .try_reserve_exact(usize::MAX)
+            .expect_err("usize::MAX should trigger an overflow!");
+
+        empty_bytes.try_reserve_exact(10).unwrap();
+        assert!(empty_bytes.capacity() >= 10);
+    }
+
+    #[test]
+    fn test_into_btree_set() {
+        let set = Set::from([3, 1, 4, 5, 9, 2, 6]);
+
+        let btree = BTreeSet::from(set.clone());
+        assert_eq!(btree.len(), set.len());
+        assert_eq!(
+            btree.iter().copied().collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5, 6, 9]
+        );
+
+        let roundtrip: Set<_> = btree.into_iter().collect();
+        assert!(roundtrip.is_subset(&set) && set.is_subset(&roundtrip));
+    }
+
+    #[test]
+    fn test_eq_btree_set() {
+        let set = Set::from([3, 1, 2]);
+
+        assert!(set.eq_btree_set(&BTreeSet::from([1, 2, 3])));
+        assert!(!set.eq_btree_set(&BTreeSet::from([1, 2, 4])));
+        assert!(!set.eq_btree_set(&BTreeSet::from([1, 2])));
+        assert!(!set.eq_btree_set(&BTreeSet::from([1, 2, 3, 4])));
+    }
+
+    #[test]
+    fn test_eq_unordered() {
+        let set = Set::from([3, 1, 2]);
+
+        assert!(set.eq_unordered(&[3, 1, 2]));
+        assert!(set.eq_unordered(&[1, 2, 3]));
+
+        assert!(!set.eq_unordered(&[1, 2, 4]));
+        assert!(!set.eq_unordered(&[1, 2]));
+        assert!(!set.eq_unordered(&[1, 2, 3, 4]));
+        assert!(!set.eq_unordered(&[1, 2, 1]));
+    }
+
     /// Ensures that, like `Vec`, `Default` works for `Set` even when its value
     /// type does not implement `Default`.
     #[test]